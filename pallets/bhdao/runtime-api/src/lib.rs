@@ -0,0 +1,14 @@
+//! Runtime API for the DAO pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use pallet_bhdao::MemberProfile;
+
+sp_api::decl_runtime_apis! {
+	pub trait BhdaoApi<AccountId> where AccountId: Codec {
+		/// The roles, transaction count and activity counters of `who`, or `None` if they are not a
+		/// member.
+		fn member_profile(who: AccountId) -> Option<MemberProfile>;
+	}
+}
@@ -0,0 +1,127 @@
+//! Fee waiver for DAO voting.
+//!
+//! `WaiveVotingFees` wraps the runtime's fee-charging extension. Voting and finalization calls from
+//! members skip it while the member has `FreeCallsPerEra` calls left; every other transaction is
+//! handed to the wrapped extension unchanged.
+//!
+//! The extension takes the wrapped extension's identifier and metadata, and encodes the same way,
+//! so it can replace it in `SignedExtra` without changing the transaction format.
+
+use crate::pallet::{Call, Config, Pallet};
+use codec::{Decode, Encode};
+use frame_support::traits::IsSubType;
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{DispatchInfoOf, PostDispatchInfoOf, SignedExtension, SignedExtensionMetadata, UniqueSaturatedInto},
+	transaction_validity::{TransactionPriority, TransactionValidity, TransactionValidityError, ValidTransaction},
+	DispatchResult,
+};
+use sp_std::{fmt, marker::PhantomData, vec::Vec};
+
+/// Priority of waived calls. They pay no fee, so they never outrank a transaction that does.
+pub const FREE_CALL_PRIORITY: TransactionPriority = 0;
+
+/// Waives the fees of `S` for members' voting and finalization calls, up to a quota per era.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+pub struct WaiveVotingFees<T, S>(pub S, PhantomData<T>);
+
+impl<T, S> WaiveVotingFees<T, S> {
+	pub fn new(inner: S) -> Self {
+		Self(inner, PhantomData)
+	}
+}
+
+impl<T, S> From<S> for WaiveVotingFees<T, S> {
+	fn from(inner: S) -> Self {
+		Self::new(inner)
+	}
+}
+
+impl<T, S: fmt::Debug> fmt::Debug for WaiveVotingFees<T, S> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "WaiveVotingFees({:?})", self.0)
+	}
+}
+
+impl<T: Config + Send + Sync, S> WaiveVotingFees<T, S>
+where
+	<T as frame_system::Config>::Call: IsSubType<Call<T>>,
+{
+	/// The era and the count of the free call `call` would use, if its fee is waived.
+	fn waived_call(who: &T::AccountId, call: &<T as frame_system::Config>::Call) -> Option<(T::BlockNumber, u32)> {
+		match call.is_sub_type() {
+			Some(call) if Pallet::<T>::is_fee_free_call(call) => Pallet::<T>::next_free_call(who),
+			_ => None,
+		}
+	}
+}
+
+impl<T, S> SignedExtension for WaiveVotingFees<T, S>
+where
+	T: Config + Send + Sync,
+	<T as frame_system::Config>::Call: IsSubType<Call<T>>,
+	S: SignedExtension<AccountId = T::AccountId, Call = <T as frame_system::Config>::Call>,
+{
+	const IDENTIFIER: &'static str = S::IDENTIFIER;
+	type AccountId = T::AccountId;
+	type Call = <T as frame_system::Config>::Call;
+	type AdditionalSigned = S::AdditionalSigned;
+	/// `None` when the fee was waived.
+	type Pre = Option<S::Pre>;
+
+	fn additional_signed(&self) -> Result<Self::AdditionalSigned, TransactionValidityError> {
+		self.0.additional_signed()
+	}
+
+	fn metadata() -> Vec<SignedExtensionMetadata> {
+		S::metadata()
+	}
+
+	fn validate(
+		&self,
+		who: &Self::AccountId,
+		call: &Self::Call,
+		info: &DispatchInfoOf<Self::Call>,
+		len: usize,
+	) -> TransactionValidity {
+		// Only one waived call per free call slot can be in the pool at a time, and it is dropped
+		// when the era ends
+		if let Some((era, used)) = Self::waived_call(who, call) {
+			return ValidTransaction::with_tag_prefix("WaiveVotingFees")
+				.priority(FREE_CALL_PRIORITY)
+				.and_provides((who, era, used))
+				.longevity(Pallet::<T>::fee_era_blocks_left(era).unique_saturated_into())
+				.build();
+		}
+		self.0.validate(who, call, info, len)
+	}
+
+	fn pre_dispatch(
+		self,
+		who: &Self::AccountId,
+		call: &Self::Call,
+		info: &DispatchInfoOf<Self::Call>,
+		len: usize,
+	) -> Result<Self::Pre, TransactionValidityError> {
+		if Self::waived_call(who, call).is_some() {
+			Pallet::<T>::use_free_call(who);
+			return Ok(None);
+		}
+		self.0.pre_dispatch(who, call, info, len).map(Some)
+	}
+
+	fn post_dispatch(
+		pre: Option<Self::Pre>,
+		info: &DispatchInfoOf<Self::Call>,
+		post_info: &PostDispatchInfoOf<Self::Call>,
+		len: usize,
+		result: &DispatchResult,
+	) -> Result<(), TransactionValidityError> {
+		match pre {
+			Some(None) => Ok(()),
+			Some(Some(pre)) => S::post_dispatch(Some(pre), info, post_info, len, result),
+			None => S::post_dispatch(None, info, post_info, len, result),
+		}
+	}
+}
@@ -2,6 +2,9 @@
 
 pub use pallet::*;
 
+pub mod fees;
+pub mod migrations;
+
 #[cfg(test)]
 mod mock;
 
@@ -14,21 +17,43 @@ mod tests;
 #[frame_support::pallet]
 pub mod pallet {
 	use frame_support::{
+		dispatch::{Dispatchable, PostDispatchInfo},
 		pallet_prelude::*,
-		traits::Currency,
+		traits::{
+			schedule::{DispatchTime, Named as ScheduleNamed, Priority},
+			Currency, EnsureOrigin, Randomness, ReservableCurrency,
+		},
+		transactional,
+		weights::{extract_actual_weight, GetDispatchInfo},
 	};
 	use frame_system::pallet_prelude::*;
 	use scale_info::{
 		TypeInfo,
 	};
-	use sp_runtime::ArithmeticError;
-	use sp_std::vec::Vec;
+	use sp_runtime::{traits::{One, Saturating, Zero}, ArithmeticError, Perbill};
+	use sp_std::{boxed::Box, vec::Vec};
 
 
 	#[cfg(feature = "std")]
 	use frame_support::serde::{Deserialize, Serialize};
 
-	//type BalanceOf<T> = <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+	/// Prefix of the scheduler task names used to enact proposals.
+	pub const PROPOSAL_TASK_ID: &[u8] = b"bhdao/proposal";
+
+	/// Prefix of the scheduler task names used to finalize voting rounds.
+	pub const ROUND_TASK_ID: &[u8] = b"bhdao/round";
+
+	/// Prefix of the scheduler task names used to draw review panels.
+	pub const PANEL_TASK_ID: &[u8] = b"bhdao/panel";
+
+	/// Scheduler priority of the tasks the pallet schedules, the middle of the range.
+	pub const SCHEDULE_PRIORITY: Priority = 63;
+
+	/// Number of blocks past the end of a round in which its finalization can be scheduled,
+	/// when the first ones already hold `MaxRoundsPerBlock` rounds.
+	pub const ROUND_SCHEDULING_ATTEMPTS: u32 = 10;
+
+	type BalanceOf<T> = <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
 
 	#[pallet::type_value]
     pub fn DefaultQualificationVotingWindow<T: Config>() -> u32
@@ -54,6 +79,32 @@ pub mod pallet {
         0u32
     }
 
+	#[pallet::type_value]
+    pub fn DefaultScoreThresholds<T: Config>() -> Scorecard
+    {
+        let max = T::MaxScore::get();
+        let half = max / 2 + max % 2;
+        Scorecard { authenticity: half, relevance: half, completeness: half, originality: half }
+    }
+
+	#[pallet::type_value]
+    pub fn DefaultProposalVotingWindow<T: Config>() -> u32
+    {
+        14400u32
+    }
+
+	#[pallet::type_value]
+    pub fn DefaultProposalQuorum<T: Config>() -> u32
+    {
+        0u32
+    }
+
+	#[pallet::type_value]
+    pub fn DefaultProposalThreshold<T: Config>() -> Perbill
+    {
+        Perbill::from_percent(50)
+    }
+
 	#[derive(Clone, Encode, Decode, PartialEq, Debug, TypeInfo, Eq)]
 	#[scale_info(skip_type_params(T))]
 	pub struct Document<T:Config> {
@@ -63,6 +114,9 @@ pub mod pallet {
 		pub format: Vec<u8>,
 		pub hash: Vec<u8>,
 		pub status: DocumentStatus,
+		/// Mean qualification scores, per criterion, from the document's latest finalized
+		/// qualification round.
+		pub scores: Option<Scorecard>,
 	}
 
 	#[derive(Clone, Encode, Decode, PartialEq, Debug, TypeInfo, Eq)]
@@ -76,6 +130,99 @@ pub mod pallet {
 		pub status: VoteStatus,
 	}
 
+	/// A verified document shown in an exhibition, with the curator's annotation.
+	#[derive(Clone, Encode, Decode, PartialEq, Debug, TypeInfo, Eq)]
+	#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+	pub struct Exhibit {
+		pub document_id: u64,
+		pub annotation: Vec<u8>,
+	}
+
+	/// An ordered collection of verified documents curated by a collector.
+	#[derive(Clone, Encode, Decode, PartialEq, Debug, TypeInfo, Eq)]
+	#[scale_info(skip_type_params(T))]
+	pub struct Exhibition<T:Config> {
+		pub curator: T::AccountId,
+		pub name: Vec<u8>,
+		pub note: Vec<u8>,
+		pub cover: Option<u64>,
+		pub exhibits: Vec<Exhibit>,
+		pub published: bool,
+	}
+
+	/// What remains of a finalized round once its ballots are pruned.
+	#[derive(Clone, Encode, Decode, PartialEq, Debug, TypeInfo, Eq)]
+	pub struct RoundSummary<BlockNumber> {
+		pub yes_votes: u64,
+		pub no_votes: u64,
+		pub status: VoteStatus,
+		pub finalized_at: BlockNumber,
+	}
+
+	/// What an account has done in the DAO.
+	#[derive(Clone, Encode, Decode, PartialEq, Debug, TypeInfo, Eq, Default)]
+	#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+	pub struct Activity {
+		pub documents_submitted: u32,
+		pub documents_verified: u32,
+		pub votes_cast: u32,
+		/// Ballots that matched the outcome of a round decided on the votes.
+		pub winning_votes: u32,
+	}
+
+	/// A member's roles and activity, as shown by the runtime API.
+	#[derive(Clone, Encode, Decode, PartialEq, Debug, TypeInfo, Eq)]
+	#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+	pub struct MemberProfile {
+		pub roles: Vec<Roles>,
+		pub transactions: u64,
+		pub activity: Activity,
+	}
+
+	/// An appeal against a rejection, and the round it opened.
+	#[derive(Clone, Encode, Decode, PartialEq, Debug, TypeInfo, Eq)]
+	#[scale_info(skip_type_params(T))]
+	pub struct Appeal<T:Config> {
+		pub vote_type: VoteType,
+		pub voting_id: u64,
+		pub deposit: BalanceOf<T>,
+		pub outcome: Option<VoteStatus>,
+		/// The rejection being appealed, which the document returns to if the round expires.
+		pub prior_status: DocumentStatus,
+	}
+
+	/// Why a member flags a verified document.
+	#[derive(Clone, Encode, Decode, PartialEq, Debug, TypeInfo, Eq, Copy)]
+	#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+	pub enum FlagReason {
+		Forged,
+		Legal,
+		Privacy,
+	}
+
+	/// A flag raised on a verified document, with an optional IPFS hash of the evidence. Flags
+	/// are kept once the review they opened concludes.
+	#[derive(Clone, Encode, Decode, PartialEq, Debug, TypeInfo, Eq)]
+	#[scale_info(skip_type_params(T))]
+	pub struct Flag<T:Config> {
+		pub flagger: T::AccountId,
+		pub reason: FlagReason,
+		pub note: Option<Vec<u8>>,
+		pub deposit: BalanceOf<T>,
+		pub review: Option<u64>,
+		pub outcome: Option<VoteStatus>,
+	}
+
+	/// A call submitted to the DAO. It is dispatched with the `Dao` origin once its vote passes,
+	/// after `enactment_delay` blocks if that is not zero.
+	#[derive(Clone, Encode, Decode, PartialEq, Debug, TypeInfo, Eq)]
+	#[scale_info(skip_type_params(T))]
+	pub struct DaoProposal<T:Config> {
+		pub proposer: T::AccountId,
+		pub call: <T as Config>::Proposal,
+		pub enactment_delay: T::BlockNumber,
+	}
+
 	#[derive(Clone, Encode, Decode, PartialEq, Debug, TypeInfo, Eq, Copy)]
 	#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 	pub enum Roles {
@@ -102,6 +249,12 @@ pub mod pallet {
 		VoteInProgress,
 		Verified,
 		Rejected,
+		/// Rejected because too few members voted, rather than on the merits.
+		QuorumNotReached,
+		/// Verified, then shown to be forged on review.
+		Revoked,
+		/// Verified, then withdrawn from display on review for legal or privacy reasons.
+		Quarantined,
 	}
 
 	#[derive(Clone, Encode, Decode, PartialEq, Debug, TypeInfo, Eq, Copy)]
@@ -110,20 +263,244 @@ pub mod pallet {
 		Qualification,
 		Verification,
 		Proposal,
+		Review,
+	}
+
+	#[derive(Clone, Encode, Decode, PartialEq, Debug, TypeInfo, Eq, Copy)]
+	#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+	pub enum ReasonCode {
+		Duplicate,
+		Inauthentic,
+		Inappropriate,
+		Incomplete,
+	}
+
+	/// A qualifier's marks for a document, each from zero to `MaxScore`. Also used for the
+	/// per-criterion thresholds and for the mean scores stored on a document.
+	#[derive(Clone, Encode, Decode, PartialEq, Debug, TypeInfo, Eq, Copy, Default)]
+	#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+	pub struct Scorecard {
+		pub authenticity: u8,
+		pub relevance: u8,
+		pub completeness: u8,
+		pub originality: u8,
+	}
+
+	impl Scorecard {
+		pub fn scores(&self) -> [u8; 4] {
+			[self.authenticity, self.relevance, self.completeness, self.originality]
+		}
+
+		/// Whether every criterion reaches its threshold.
+		pub fn meets(&self, thresholds: &Scorecard) -> bool {
+			self.scores().iter().zip(thresholds.scores().iter()).all(|(score, threshold)| score >= threshold)
+		}
+	}
+
+	/// Reason a qualifier attaches to a ballot, with an optional IPFS hash of a longer note.
+	#[derive(Clone, Encode, Decode, PartialEq, Debug, TypeInfo, Eq)]
+	#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+	pub struct Rationale {
+		pub reason: ReasonCode,
+		pub note: Option<Vec<u8>>,
+	}
+
+	/// A voting round held on a document, as returned by `get_document_history`.
+	#[derive(Clone, Encode, Decode, PartialEq, Debug, TypeInfo, Eq)]
+	#[scale_info(skip_type_params(T))]
+	pub struct VotingRound<T:Config> {
+		pub vote_type: VoteType,
+		pub voting_id: u64,
+		pub vote: Vote<T>,
+		pub rationales: Vec<(T::AccountId, Rationale)>,
+	}
+
+	/// Tells whether two accounts are controlled by the same party.
+	pub trait RelatedAccounts<AccountId> {
+		fn are_related(a: &AccountId, b: &AccountId) -> bool;
+	}
+
+	impl<AccountId> RelatedAccounts<AccountId> for () {
+		fn are_related(_a: &AccountId, _b: &AccountId) -> bool {
+			false
+		}
+	}
+
+	/// Origin for calls dispatched by the DAO itself.
+	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, Eq)]
+	pub enum RawOrigin {
+		/// A proposal passed by the DAO members.
+		Dao,
 	}
 
+	#[pallet::origin]
+	pub type Origin = RawOrigin;
+
 	/// Configure the pallet by specifying the parameters and types on which it depends.
 	#[pallet::config]
 	pub trait Config: frame_system::Config +pallet_nft::Config {
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
-		type Currency: Currency<Self::AccountId>;
+		type Currency: ReservableCurrency<Self::AccountId>;
+
+		/// The outer origin type, built from `RawOrigin::Dao` when a proposal is executed.
+		type Origin: From<RawOrigin>;
+
+		/// The outer call type that DAO proposals are made of.
+		type Proposal: Parameter
+			+ Dispatchable<Origin = <Self as Config>::Origin, PostInfo = PostDispatchInfo>
+			+ GetDispatchInfo
+			+ From<Call<Self>>;
+
+		/// The caller origin, overarching type of all pallets origins.
+		type PalletsOrigin: From<RawOrigin> + From<frame_system::RawOrigin<Self::AccountId>>;
+
+		/// Scheduler used to enact passed proposals that have an enactment delay, and to finalize
+		/// qualification and verification rounds when their window closes.
+		type Scheduler: ScheduleNamed<Self::BlockNumber, <Self as Config>::Proposal, Self::PalletsOrigin>;
+
+		/// Origin allowed to manage membership and change the DAO parameters.
+		type GovernanceOrigin: EnsureOrigin<<Self as frame_system::Config>::Origin>;
+
+		/// Origin of the DAO administrator, also allowed to manage membership and change the DAO
+		/// parameters. `EnsureKey` backs it with the `Key` account.
+		type AdminOrigin: EnsureOrigin<<Self as frame_system::Config>::Origin>;
+
+		/// Amount reserved from a document creator for each appeal.
+		#[pallet::constant]
+		type AppealDeposit: Get<BalanceOf<Self>>;
+
+		/// Maximum number of appeals per document.
+		#[pallet::constant]
+		type MaxAppeals: Get<u32>;
+
+		/// Extra votes an appeal round needs to reach quorum, per appeal attempt.
+		#[pallet::constant]
+		type AppealQuorumIncrease: Get<u32>;
+
+		/// Amount reserved from a member who flags a document. It is slashed if the review the
+		/// flag leads to keeps the document verified.
+		#[pallet::constant]
+		type FlagDeposit: Get<BalanceOf<Self>>;
+
+		/// Number of flags on a verified document that opens a review vote.
+		#[pallet::constant]
+		type FlagThreshold: Get<u32>;
+
+		/// Number of qualifier tokens the role collection is created with.
+		#[pallet::constant]
+		type MaxQualifiers: Get<u32>;
+
+		/// Number of collector tokens the role collection is created with.
+		#[pallet::constant]
+		type MaxCollectors: Get<u32>;
+
+		/// Number of contributor tokens the role collection is created with.
+		#[pallet::constant]
+		type MaxContributors: Get<u32>;
+
+		/// Maximum number of members a role can have. Role caps cannot be raised past it.
+		#[pallet::constant]
+		type MaxMembers: Get<u32>;
+
+		/// Maximum number of ballots a round takes. Finalizing a round credits each ballot, so
+		/// this bounds its weight.
+		#[pallet::constant]
+		type MaxVotesPerRound: Get<u32>;
+
+		/// Maximum number of members `add_members` takes at once.
+		#[pallet::constant]
+		type MaxBatchSize: Get<u32>;
+
+		/// Maximum number of documents in an exhibition.
+		#[pallet::constant]
+		type MaxExhibits: Get<u32>;
+
+		/// Number of blocks the ballots of a finalized round are kept before `on_idle` prunes them.
+		#[pallet::constant]
+		type BallotRetentionPeriod: Get<Self::BlockNumber>;
+
+		/// Number of blocks after a voting window closes during which the round can still be
+		/// finalized. After that it expires.
+		#[pallet::constant]
+		type ExpiryGracePeriod: Get<Self::BlockNumber>;
+
+		/// Number of rounds the scheduler finalizes in one block. Rounds ending in a full block
+		/// are finalized in one of the next ones.
+		#[pallet::constant]
+		type MaxRoundsPerBlock: Get<u32>;
+
+		/// Source of randomness used to draw qualification review panels.
+		type Randomness: Randomness<Self::Hash, Self::BlockNumber>;
+
+		/// Blocks between opening a qualification round and drawing its review panel. It should be
+		/// long enough that `Randomness` has changed since the round opened.
+		#[pallet::constant]
+		type PanelDrawDelay: Get<Self::BlockNumber>;
+
+		/// Number of open review panels a qualifier can sit on at once.
+		#[pallet::constant]
+		type MaxPanelAssignments: Get<u32>;
+
+		/// Accounts the runtime knows to act for one another, such as proxies. They are treated
+		/// like the document creator when checking for conflicts of interest.
+		type Relations: RelatedAccounts<Self::AccountId>;
+
+		/// Maximum length of the IPFS hash attached to a ballot rationale.
+		#[pallet::constant]
+		type MaxRationaleNoteLength: Get<u32>;
+
+		/// Highest score a qualifier can give a document on any criterion.
+		#[pallet::constant]
+		type MaxScore: Get<u8>;
+
+		/// Number of voting and finalization calls a member can make without fees in each era.
+		#[pallet::constant]
+		type FreeCallsPerEra: Get<u32>;
+
+		/// Length in blocks of the era `FreeCallsPerEra` applies to.
+		#[pallet::constant]
+		type FreeCallEraLength: Get<Self::BlockNumber>;
 	}
 
+	/// The current storage version.
+	pub(crate) const STORAGE_VERSION: StorageVersion = StorageVersion::new(5);
+
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	#[pallet::without_storage_info]
 	pub struct Pallet<T>(_);
 
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			RoundsScheduledAt::<T>::remove(now);
+			let rounds = ExpiringRounds::<T>::take(now);
+			let mut expired = 0;
+			for (vote_type, voting_id) in rounds.iter() {
+				if Self::do_expire_vote(*vote_type,*voting_id).is_ok() {
+					expired += 1;
+				}
+			}
+			T::DbWeight::get().reads_writes(1 + rounds.len() as u64 * 2, 2 + expired * 5)
+		}
+
+		fn on_idle(now: T::BlockNumber, remaining_weight: Weight) -> Weight {
+			Self::prune_ballots(now, remaining_weight)
+		}
+	}
+
+	/// Number of rounds whose finalization is scheduled at a block.
+	#[pallet::storage]
+	#[pallet::getter(fn get_rounds_scheduled_at)]
+	pub(super) type RoundsScheduledAt<T:Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::BlockNumber,
+		u32,
+		ValueQuery,
+	>;
+
 	#[pallet::storage]
 	#[pallet::getter(fn get_key)]
 	pub(super) type Key<T:Config> = StorageValue<_, T::AccountId,OptionQuery>;
@@ -172,6 +549,45 @@ pub mod pallet {
 	#[pallet::getter(fn get_verification_quorum)]
 	pub(super) type VerificationQuorum<T> = StorageValue<_, u32,ValueQuery,DefaultVerificationQuorum<T>>;
 
+	#[pallet::storage]
+	#[pallet::getter(fn get_proposal_voting_window)]
+	pub(super) type ProposalVotingWindow<T> = StorageValue<_, u32,ValueQuery,DefaultProposalVotingWindow<T>>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn get_proposal_quorum)]
+	pub(super) type ProposalQuorum<T> = StorageValue<_, u32,ValueQuery,DefaultProposalQuorum<T>>;
+
+	/// Share of the cast votes that must be in favour for a proposal to pass.
+	#[pallet::storage]
+	#[pallet::getter(fn get_proposal_threshold)]
+	pub(super) type ProposalThreshold<T> = StorageValue<_, Perbill,ValueQuery,DefaultProposalThreshold<T>>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn get_proposal_count)]
+	pub(super) type ProposalsCount<T> = StorageValue<_, u64,ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn get_proposal)]
+	pub(super) type Proposals<T:Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		u64,
+		DaoProposal<T>,
+		OptionQuery,
+	>;
+
+	/// Votes on proposals, keyed by proposal id. The `document_id` of each vote holds the
+	/// proposal id.
+	#[pallet::storage]
+	#[pallet::getter(fn get_proposal_vote)]
+	pub(super) type ProposalVotes<T:Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		u64,
+		Vote<T>,
+		OptionQuery,
+	>;
+
 	#[pallet::storage]
 	#[pallet::getter(fn get_transactions_per_address)]
 	pub(super) type TransactionsPerAddress<T:Config> = StorageMap<
@@ -212,104 +628,446 @@ pub mod pallet {
 		OptionQuery,
 	>;
 
+	/// Ballots, keyed by round then voter. They are pruned `BallotRetentionPeriod` blocks after
+	/// the round is finalized, leaving its `RoundSummaries` record.
 	#[pallet::storage]
 	#[pallet::getter(fn get_member_vote)]
-	pub(super) type MemberVote<T:Config> = StorageMap<
+	pub(super) type MemberVotes<T:Config> = StorageNMap<
 		_,
-		Blake2_128Concat,
-		(T::AccountId,VoteType,u64),
+		(
+			NMapKey<Blake2_128Concat, VoteType>,
+			NMapKey<Blake2_128Concat, u64>,
+			NMapKey<Blake2_128Concat, T::AccountId>,
+		),
 		bool,
 		OptionQuery,
 	>;
 
 	#[pallet::storage]
-	#[pallet::getter(fn get_all_qualifiers)]
-	pub(super) type Qualifiers<T:Config> = StorageValue<_, Vec<T::AccountId>, ValueQuery>;
+	#[pallet::getter(fn get_round_summary)]
+	pub(super) type RoundSummaries<T:Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		VoteType,
+		Blake2_128Concat,
+		u64,
+		RoundSummary<T::BlockNumber>,
+		OptionQuery,
+	>;
 
+	/// Finalized rounds whose ballots have not been pruned yet, by the block their
+	/// `BallotRetentionPeriod` ends.
 	#[pallet::storage]
-	#[pallet::getter(fn get_all_collectors)]
-	pub(super) type Collectors<T:Config> = StorageValue<_, Vec<T::AccountId>, ValueQuery>;
+	#[pallet::getter(fn get_prune_queue)]
+	pub(super) type PruneQueue<T:Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::BlockNumber,
+		Vec<(VoteType,u64)>,
+		ValueQuery,
+	>;
 
+	/// First block whose `PruneQueue` entry `on_idle` has not cleared yet.
 	#[pallet::storage]
-	#[pallet::getter(fn get_all_contributors)]
-	pub(super) type Contributors<T:Config> = StorageValue<_, Vec<T::AccountId>, ValueQuery>;
+	#[pallet::getter(fn get_prune_cursor)]
+	pub(super) type PruneCursor<T:Config> = StorageValue<_, T::BlockNumber, ValueQuery>;
 
+	/// Rounds to expire in `on_initialize` at a given block, unless they were finalized first.
+	#[pallet::storage]
+	#[pallet::getter(fn get_expiring_rounds)]
+	pub(super) type ExpiringRounds<T:Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::BlockNumber,
+		Vec<(VoteType,u64)>,
+		ValueQuery,
+	>;
 
-	// Pallets use events to inform users when important changes are made.
-	// https://docs.substrate.io/v3/runtime/events-and-errors
-	#[pallet::event]
-	#[pallet::generate_deposit(pub(super) fn deposit_event)]
-	pub enum Event<T: Config> {
-		QualifierAdded(T::AccountId,u32),
-		CollectorAdded(T::AccountId,u32),
-		ContributorAdded(T::AccountId,u32),
-		DocumentCreated(T::AccountId,u64),
-		DocumentStatusUpdated(u64,u8),
-		QualificationVotingWindowChanged(u32),
-		QualificationVotingStarted(u64),
-		VerificationVotingWindowChanged(u32),
-		VerificationVotingStarted(u64),
-		QualificationVotingEnded(u64),
-		VerificationVotingEnded(u64),
-		QualificationQuorumChanged(u32),
-		VerificationQuorumChanged(u32),
-		VoteCast(u8,u64),
-	}
+	#[pallet::storage]
+	#[pallet::getter(fn get_rationale)]
+	pub(super) type QualificationRationales<T:Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		u64,
+		Blake2_128Concat,
+		T::AccountId,
+		Rationale,
+		OptionQuery,
+	>;
 
-	// Errors inform users that something went wrong.
-	#[pallet::error]
-	pub enum Error<T> {
-		QualifierAlreadyExists,
-		CollectorAlreadyExists,
-		ContributorAlreadyExists,
-		NotAQualifier,
-		NotACollector,
-		NotAContributor,
-		NotAuthorized,
-		DocumentNotFound,
-		IncorrectDocumentStatus,
-		DocumentTitleNotProvided,
-		DocumentDescriptionNotProvided,
-		DocumentFormatNotProvided,
-		DocumentIPFSHashNotProvided,
-		VerificationVoteAlreadyCreated,
-		VotingWindowNotValid,
-		DocumentNotReviewed,
-		VoteNotFound,
-		VoteNotInProgress,
-		VoteStillInProgress,
-		DocumentNotUnderReview,
-		MemberAlreadyVoted,
-	}
+	#[pallet::storage]
+	#[pallet::getter(fn get_document_rounds)]
+	pub(super) type DocumentRounds<T:Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		u64,
+		Vec<(VoteType,u64)>,
+		ValueQuery,
+	>;
 
-	// Dispatchable functions allows users to interact with the pallet and invoke state changes.
-	// These functions materialize as "extrinsics", which are often compared to transactions.
-	// Dispatchable functions must be annotated with a weight and must return a DispatchResult.
-	#[pallet::call]
-	impl<T: Config> Pallet<T> {
-		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(4,3))]
-		pub fn init_collections(origin: OriginFor<T>) -> DispatchResult {
-			ensure_root(origin.clone())?;
-			let max_qualifiers: u32 = 200;
-			let max_collectors : u32 = 100;
-			let max_contributors : u32 = 1000;
+	#[pallet::storage]
+	#[pallet::getter(fn get_appeals)]
+	pub(super) type Appeals<T:Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		u64,
+		Vec<Appeal<T>>,
+		ValueQuery,
+	>;
 
-			// create qualifiers collection
-			pallet_nft::Pallet::<T>::create_collection(origin.clone(),Roles::QualifierRole as u32,max_qualifiers,b"Qualifiers".to_vec()).ok();
+	#[pallet::storage]
+	#[pallet::getter(fn get_flags)]
+	pub(super) type Flags<T:Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		u64,
+		Vec<Flag<T>>,
+		ValueQuery,
+	>;
 
-			//create collectors collection
-			pallet_nft::Pallet::<T>::create_collection(origin.clone(),Roles::CollectorRole as u32,max_collectors,b"Collectors".to_vec()).ok();
+	#[pallet::storage]
+	#[pallet::getter(fn get_review_vote_count)]
+	pub(super) type ReviewVotesCount<T> = StorageValue<_, u64,ValueQuery>;
 
-			//create contributors collection
-			pallet_nft::Pallet::<T>::create_collection(origin.clone(),Roles::ContributorRole as u32,max_contributors,b"Contributors".to_vec()).ok();
+	#[pallet::storage]
+	#[pallet::getter(fn get_review_vote)]
+	pub(super) type ReviewVotes<T:Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		u64,
+		Vote<T>,
+		OptionQuery,
+	>;
 
-			Ok(())
-		}
+	/// The review vote open on a document, if any.
+	#[pallet::storage]
+	#[pallet::getter(fn get_active_review)]
+	pub(super) type ActiveReviews<T:Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		u64,
+		u64,
+		OptionQuery,
+	>;
 
-		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(4,3))]
-		pub fn add_qualifier(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
-			ensure_root(origin.clone())?;
-			let mut qualifiers = Qualifiers::<T>::get();
+	/// Quorum of rounds that do not use the default one, such as appeal rounds.
+	#[pallet::storage]
+	#[pallet::getter(fn get_round_quorum)]
+	pub(super) type RoundQuorums<T:Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		VoteType,
+		Blake2_128Concat,
+		u64,
+		u32,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn get_exhibitions_count)]
+	pub(super) type ExhibitionsCount<T> = StorageValue<_, u64,ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn get_exhibition)]
+	pub(super) type Exhibitions<T:Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		u64,
+		Exhibition<T>,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn get_curator_exhibitions)]
+	pub(super) type CuratorExhibitions<T:Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Vec<u64>,
+		ValueQuery,
+	>;
+
+	/// Minimum mean score, per criterion, for a document to pass qualification.
+	#[pallet::storage]
+	#[pallet::getter(fn get_score_thresholds)]
+	pub(super) type ScoreThresholds<T> = StorageValue<_, Scorecard, ValueQuery, DefaultScoreThresholds<T>>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn get_scorecard)]
+	pub(super) type Scorecards<T:Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		u64,
+		Blake2_128Concat,
+		T::AccountId,
+		Scorecard,
+		OptionQuery,
+	>;
+
+	/// Sum of the scorecards cast in a qualification round, per criterion, and the number of
+	/// ballots, kept up to date as ballots are cast.
+	#[pallet::storage]
+	#[pallet::getter(fn get_score_totals)]
+	pub(super) type ScoreTotals<T:Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		u64,
+		([u32; 4],u32),
+		ValueQuery,
+	>;
+
+	/// Number of qualifiers drawn to review each qualification round. When zero, every qualifier
+	/// can vote.
+	#[pallet::storage]
+	#[pallet::getter(fn get_review_panel_size)]
+	pub(super) type ReviewPanelSize<T> = StorageValue<_, u32, ValueQuery>;
+
+	/// The qualifiers drawn to review a qualification round.
+	#[pallet::storage]
+	#[pallet::getter(fn get_review_panel)]
+	pub(super) type ReviewPanels<T:Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		u64,
+		Vec<T::AccountId>,
+		OptionQuery,
+	>;
+
+	/// Size of the review panel still to be drawn for a qualification round.
+	#[pallet::storage]
+	#[pallet::getter(fn get_pending_panel)]
+	pub(super) type PendingPanels<T:Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		u64,
+		u32,
+		OptionQuery,
+	>;
+
+	/// Number of open review panels each qualifier sits on.
+	#[pallet::storage]
+	#[pallet::getter(fn get_workload)]
+	pub(super) type Workloads<T:Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		u32,
+		ValueQuery,
+	>;
+
+	/// Activity counters of every account that used the pallet.
+	#[pallet::storage]
+	#[pallet::getter(fn get_activity)]
+	pub(super) type Activities<T:Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Activity,
+		ValueQuery,
+	>;
+
+	/// Era of each member's last fee-free call, and how many they made in it.
+	#[pallet::storage]
+	#[pallet::getter(fn get_free_calls)]
+	pub(super) type FreeCalls<T:Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		(T::BlockNumber,u32),
+		ValueQuery,
+	>;
+
+	/// Qualifiers who declared a conflict of interest on a document.
+	#[pallet::storage]
+	#[pallet::getter(fn get_recusal)]
+	pub(super) type Recusals<T:Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		u64,
+		Blake2_128Concat,
+		T::AccountId,
+		(),
+		OptionQuery,
+	>;
+
+	/// Number of `Recusals` on a document, so quorums don't have to count them.
+	#[pallet::storage]
+	#[pallet::getter(fn get_recusal_count)]
+	pub(super) type RecusalCounts<T:Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		u64,
+		u32,
+		ValueQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn get_all_qualifiers)]
+	pub(super) type Qualifiers<T:Config> = StorageValue<_, Vec<T::AccountId>, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn get_all_collectors)]
+	pub(super) type Collectors<T:Config> = StorageValue<_, Vec<T::AccountId>, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn get_all_contributors)]
+	pub(super) type Contributors<T:Config> = StorageValue<_, Vec<T::AccountId>, ValueQuery>;
+
+
+	// Pallets use events to inform users when important changes are made.
+	// https://docs.substrate.io/v3/runtime/events-and-errors
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		QualifierAdded(T::AccountId,u32),
+		CollectorAdded(T::AccountId,u32),
+		ContributorAdded(T::AccountId,u32),
+		DocumentCreated(T::AccountId,u64),
+		DocumentStatusUpdated(u64,u8),
+		QualificationVotingWindowChanged(u32),
+		QualificationVotingStarted(u64),
+		VerificationVotingWindowChanged(u32),
+		VerificationVotingStarted(u64),
+		QualificationVotingEnded(u64),
+		VerificationVotingEnded(u64),
+		QualificationQuorumChanged(u32),
+		VerificationQuorumChanged(u32),
+		VoteCast(u8,u64),
+		ProposalSubmitted(T::AccountId,u64),
+		ProposalVotingEnded(u64,VoteStatus),
+		ProposalExecuted(u64,DispatchResult),
+		ProposalScheduled(u64,T::BlockNumber),
+		ProposalVotingWindowChanged(u32),
+		ProposalQuorumChanged(u32),
+		ProposalThresholdChanged(Perbill),
+		DocumentAppealed(u64,u64),
+		AppealConcluded(u64,VoteStatus),
+		ExhibitionCreated(T::AccountId,u64),
+		ExhibitionUpdated(u64),
+		ExhibitionPublished(u64,bool),
+		ConflictDeclared(T::AccountId,u64),
+		VoteExpired(VoteType,u64),
+		ReviewPanelSizeChanged(u32),
+		ReviewPanelDrawn(u64,Vec<T::AccountId>),
+		ScoreThresholdsChanged(Scorecard),
+		DocumentFlagged(T::AccountId,u64,FlagReason),
+		ReviewVotingStarted(u64,u64),
+		ReviewVotingEnded(u64),
+		KeyChanged(T::AccountId),
+		RoleChanged(T::AccountId,Roles,Roles),
+		RoleCapChanged(Roles,u32),
+		RoundNotScheduled(VoteType,u64),
+		BadgeReissued(T::AccountId,Roles),
+	}
+
+	// Errors inform users that something went wrong.
+	#[pallet::error]
+	pub enum Error<T> {
+		QualifierAlreadyExists,
+		CollectorAlreadyExists,
+		ContributorAlreadyExists,
+		NotAQualifier,
+		NotACollector,
+		NotAContributor,
+		NotAuthorized,
+		DocumentNotFound,
+		IncorrectDocumentStatus,
+		DocumentTitleNotProvided,
+		DocumentDescriptionNotProvided,
+		DocumentFormatNotProvided,
+		DocumentIPFSHashNotProvided,
+		VerificationVoteAlreadyCreated,
+		VotingWindowNotValid,
+		DocumentNotReviewed,
+		VoteNotFound,
+		VoteNotInProgress,
+		VoteStillInProgress,
+		DocumentNotUnderReview,
+		MemberAlreadyVoted,
+		RationaleNoteTooLong,
+		RationaleNoteEmpty,
+		NotAMember,
+		ProposalNotFound,
+		ProposalWeightTooHigh,
+		ProposalSchedulingFailed,
+		NotDocumentCreator,
+		DocumentNotRejected,
+		TooManyAppeals,
+		NoRoundToAppeal,
+		ExhibitionNotFound,
+		ExhibitionNameNotProvided,
+		NotTheCurator,
+		DocumentNotVerified,
+		ExhibitAlreadyAdded,
+		ExhibitNotFound,
+		TooManyExhibits,
+		CoverNotAnExhibit,
+		ConflictOfInterest,
+		ConflictDeclared,
+		VoteNotExpired,
+		NotEnoughReviewers,
+		NotOnReviewPanel,
+		ScoreOutOfRange,
+		AlreadyFlagged,
+		ReviewInProgress,
+		BatchTooLarge,
+		SameRole,
+		RoleCapNotRaised,
+		RoleCapTooHigh,
+		TooManyMembers,
+		TooManyVotes,
+		PanelNotDrawn,
+		PanelNotDue,
+		NoPendingPanel,
+		BadgeAlreadyHeld,
+	}
+
+	// Dispatchable functions allows users to interact with the pallet and invoke state changes.
+	// These functions materialize as "extrinsics", which are often compared to transactions.
+	// Dispatchable functions must be annotated with a weight and must return a DispatchResult.
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(4,3))]
+		#[transactional]
+		pub fn init_collections(origin: OriginFor<T>) -> DispatchResult {
+			Self::ensure_admin(origin)?;
+			let origin: OriginFor<T> = frame_system::RawOrigin::Root.into();
+
+			// create qualifiers collection
+			pallet_nft::Pallet::<T>::force_create_collection(origin.clone(),Roles::QualifierRole as u32,None,T::MaxQualifiers::get(),b"Qualifiers".to_vec(),false,false)?;
+
+			//create collectors collection
+			pallet_nft::Pallet::<T>::force_create_collection(origin.clone(),Roles::CollectorRole as u32,None,T::MaxCollectors::get(),b"Collectors".to_vec(),false,false)?;
+
+			//create contributors collection
+			pallet_nft::Pallet::<T>::force_create_collection(origin.clone(),Roles::ContributorRole as u32,None,T::MaxContributors::get(),b"Contributors".to_vec(),false,false)?;
+
+			Ok(())
+		}
+
+		/// Raise the number of members a role can have, by raising the supply of its token
+		/// collection.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3,1))]
+		pub fn set_role_cap(origin: OriginFor<T>, role: Roles, cap: u32) -> DispatchResult {
+			Self::ensure_admin(origin)?;
+			let collection = pallet_nft::Pallet::<T>::get_collection(role as u32).ok_or(pallet_nft::Error::<T>::CollectionDoesNotExist)?;
+			ensure!(cap > collection.total_supply,Error::<T>::RoleCapNotRaised);
+			ensure!(cap <= T::MaxMembers::get(),Error::<T>::RoleCapTooHigh);
+
+			pallet_nft::Pallet::<T>::set_total_supply(frame_system::RawOrigin::Root.into(),role as u32,cap)?;
+
+			Self::deposit_event(Event::RoleCapChanged(role,cap));
+
+			Ok(())
+		}
+
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(4,3))]
+		#[transactional]
+		pub fn add_qualifier(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+			Self::ensure_admin(origin)?;
+			let origin: OriginFor<T> = frame_system::RawOrigin::Root.into();
+			let mut qualifiers = Qualifiers::<T>::get();
+			ensure!((qualifiers.len() as u32) < T::MaxMembers::get(),Error::<T>::TooManyMembers);
 
 			let uid = Self::qualifiers_uid_count().checked_add(1).ok_or(ArithmeticError::Overflow)?;
 
@@ -320,7 +1078,7 @@ pub mod pallet {
 					Qualifiers::<T>::put(qualifiers);
 					QualifiersCount::<T>::put(uid.clone());
 					//mint NFT
-					pallet_nft::Pallet::<T>::mint(origin.clone(),Roles::QualifierRole as u32,who.clone()).ok();
+					pallet_nft::Pallet::<T>::mint(origin.clone(),Roles::QualifierRole as u32,who.clone())?;
 					Self::deposit_event(Event::QualifierAdded(who,uid));
 					Ok(())
 				}
@@ -329,9 +1087,12 @@ pub mod pallet {
 		}
 
 		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(4,3))]
+		#[transactional]
 		pub fn add_collector(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
-			ensure_root(origin.clone())?;
+			Self::ensure_admin(origin)?;
+			let origin: OriginFor<T> = frame_system::RawOrigin::Root.into();
 			let mut collectors = Collectors::<T>::get();
+			ensure!((collectors.len() as u32) < T::MaxMembers::get(),Error::<T>::TooManyMembers);
 
 			let uid = Self::collectors_uid_count().checked_add(1).ok_or(ArithmeticError::Overflow)?;
 
@@ -342,7 +1103,7 @@ pub mod pallet {
 					Collectors::<T>::put(collectors);
 					CollectorsCount::<T>::put(uid.clone());
 					//mint NFT
-					pallet_nft::Pallet::<T>::mint(origin.clone(),Roles::CollectorRole as u32,who.clone()).ok();
+					pallet_nft::Pallet::<T>::mint(origin.clone(),Roles::CollectorRole as u32,who.clone())?;
 					Self::deposit_event(Event::CollectorAdded(who,uid));
 					Ok(())
 				}
@@ -351,9 +1112,12 @@ pub mod pallet {
 		}
 
 		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(4,3))]
+		#[transactional]
 		pub fn add_contributor(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
-			ensure_root(origin.clone())?;
+			Self::ensure_admin(origin)?;
+			let origin: OriginFor<T> = frame_system::RawOrigin::Root.into();
 			let mut contributors = Contributors::<T>::get();
+			ensure!((contributors.len() as u32) < T::MaxMembers::get(),Error::<T>::TooManyMembers);
 
 			let uid = Self::contributors_uid_count().checked_add(1).ok_or(ArithmeticError::Overflow)?;
 
@@ -364,7 +1128,7 @@ pub mod pallet {
 					Contributors::<T>::put(contributors);
 					ContributorsCount::<T>::put(uid.clone());
 					//mint NFT
-					pallet_nft::Pallet::<T>::mint(origin.clone(),Roles::ContributorRole as u32,who.clone()).ok();
+					pallet_nft::Pallet::<T>::mint(origin.clone(),Roles::ContributorRole as u32,who.clone())?;
 					Self::deposit_event(Event::ContributorAdded(who,uid));
 					Ok(())
 				}
@@ -373,6 +1137,116 @@ pub mod pallet {
 		}
 
 
+		/// Add a batch of members, each with a role, and mint their role tokens. Nothing is added
+		/// if any entry is invalid.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(6,6) + T::DbWeight::get().reads_writes(5,3).saturating_mul(members.len() as u64))]
+		#[transactional]
+		pub fn add_members(origin: OriginFor<T>, members: Vec<(T::AccountId, Roles)>) -> DispatchResult {
+			Self::ensure_admin(origin)?;
+			ensure!(members.len() as u32 <= T::MaxBatchSize::get(),Error::<T>::BatchTooLarge);
+
+			let mut qualifiers = Qualifiers::<T>::get();
+			let mut collectors = Collectors::<T>::get();
+			let mut contributors = Contributors::<T>::get();
+			for (who, role) in members.iter() {
+				let roster = match role {
+					Roles::QualifierRole => &mut qualifiers,
+					Roles::CollectorRole => &mut collectors,
+					Roles::ContributorRole => &mut contributors,
+				};
+				match roster.binary_search(who) {
+					Ok(_) => return Err(Self::member_exists_error(*role).into()),
+					Err(index) => roster.insert(index, who.clone()),
+				}
+				ensure!(roster.len() as u32 <= T::MaxMembers::get(),Error::<T>::TooManyMembers);
+			}
+
+			let origin: OriginFor<T> = frame_system::RawOrigin::Root.into();
+			let mut counts = [QualifiersCount::<T>::get(), CollectorsCount::<T>::get(), ContributorsCount::<T>::get()];
+			for (who, role) in members.into_iter() {
+				pallet_nft::Pallet::<T>::mint(origin.clone(),role as u32,who.clone())?;
+				let uid = &mut counts[role as usize - 1];
+				*uid = uid.checked_add(1).ok_or(ArithmeticError::Overflow)?;
+				Self::deposit_event(match role {
+					Roles::QualifierRole => Event::QualifierAdded(who,*uid),
+					Roles::CollectorRole => Event::CollectorAdded(who,*uid),
+					Roles::ContributorRole => Event::ContributorAdded(who,*uid),
+				});
+			}
+
+			Qualifiers::<T>::put(qualifiers);
+			Collectors::<T>::put(collectors);
+			Contributors::<T>::put(contributors);
+			QualifiersCount::<T>::put(counts[0]);
+			CollectorsCount::<T>::put(counts[1]);
+			ContributorsCount::<T>::put(counts[2]);
+
+			Ok(())
+		}
+
+		/// Move a member from one role to another, burning the token of the old role and minting
+		/// one for the new. The member takes the next uid of the new role.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(9,10)
+			+ T::DbWeight::get().writes(<T as pallet_nft::Config>::MaxAttributes::get() as u64))]
+		#[transactional]
+		pub fn change_role(origin: OriginFor<T>, who: T::AccountId, from: Roles, to: Roles) -> DispatchResult {
+			Self::ensure_admin(origin)?;
+			ensure!(from != to,Error::<T>::SameRole);
+
+			let mut old_roster = Self::get_roster(from);
+			let old_index = old_roster.binary_search(&who).map_err(|_| Self::not_a_member_error(from))?;
+			let mut new_roster = Self::get_roster(to);
+			let new_index = match new_roster.binary_search(&who) {
+				Ok(_) => return Err(Self::member_exists_error(to).into()),
+				Err(index) => index,
+			};
+			ensure!((new_roster.len() as u32) < T::MaxMembers::get(),Error::<T>::TooManyMembers);
+
+			if let Some(token_id) = pallet_nft::Pallet::<T>::token_of(from as u32,&who) {
+				pallet_nft::Pallet::<T>::force_burn(frame_system::RawOrigin::Root.into(),from as u32,token_id)?;
+			}
+			pallet_nft::Pallet::<T>::mint(frame_system::RawOrigin::Root.into(),to as u32,who.clone())?;
+
+			old_roster.remove(old_index);
+			new_roster.insert(new_index, who.clone());
+			Self::put_roster(from, old_roster);
+			Self::put_roster(to, new_roster);
+			Self::next_uid(to)?;
+
+			Self::deposit_event(Event::RoleChanged(who,from,to));
+
+			Ok(())
+		}
+
+		/// Mint the role NFT of a listed member who does not hold one, such as a member added before
+		/// minting failures were caught.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(8,4))]
+		#[transactional]
+		pub fn reissue_badge(origin: OriginFor<T>, who: T::AccountId, role: Roles) -> DispatchResult {
+			Self::ensure_admin(origin)?;
+			Self::get_roster(role).binary_search(&who).map_err(|_| Self::not_a_member_error(role))?;
+			ensure!(pallet_nft::Pallet::<T>::token_of(role as u32,&who).is_none(),Error::<T>::BadgeAlreadyHeld);
+
+			pallet_nft::Pallet::<T>::mint(frame_system::RawOrigin::Root.into(),role as u32,who.clone())?;
+
+			Self::deposit_event(Event::BadgeReissued(who,role));
+
+			Ok(())
+		}
+
+		/// Hand the DAO administrator key to `new`. The current key holder or the governance origin
+		/// can call this.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1))]
+		pub fn set_key(origin: OriginFor<T>, new: T::AccountId) -> DispatchResult {
+			Self::ensure_admin(origin)?;
+
+			Key::<T>::put(&new);
+
+			Self::deposit_event(Event::KeyChanged(new));
+
+			Ok(())
+		}
+
 		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(4,2))]
 		pub fn create_document(origin: OriginFor<T>, title: Vec<u8>, description: Vec<u8>,
 		format: Vec<u8>, hash: Vec<u8>) -> DispatchResult {
@@ -392,100 +1266,81 @@ pub mod pallet {
 				format: format.clone(),
 				hash: hash.clone(),
 				status: DocumentStatus::Submitted,
+				scores: None,
 			};
 
 			Documents::<T>::insert(uid.clone(),document);
 			TotalItems::<T>::put(&uid);
+			Activities::<T>::mutate(&who,|activity| activity.documents_submitted = activity.documents_submitted.saturating_add(1));
 
 			Self::deposit_event(Event::DocumentCreated(who,uid));
 
+			Self::record_transaction(&who);
 			Ok(())
 		}
 
-		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(4,4))]
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(4 + ROUND_SCHEDULING_ATTEMPTS as u64,5))]
 		pub fn create_qualification_voting(origin: OriginFor<T>, document_id: u64) -> DispatchResult{
 
 			let who = ensure_signed(origin)?;
 			ensure!(Self::ensure_qualifier(who.clone()),Error::<T>::NotAQualifier);
 
-			let mut document = Self::get_document(document_id.clone()).ok_or(Error::<T>::DocumentNotFound)?;
+			let document = Self::get_document(document_id.clone()).ok_or(Error::<T>::DocumentNotFound)?;
 
 			ensure!(document.status == DocumentStatus::Submitted, Error::<T>::VerificationVoteAlreadyCreated);
+			ensure!(!Self::has_conflict(&who,&document),Error::<T>::ConflictOfInterest);
 
-			let uid = Self::get_qualification_vote_count().checked_add(1).ok_or(ArithmeticError::Overflow)?;
-
-			let now = <frame_system::Pallet<T>>::block_number();
-
-			let end = now + QualificationVotingWindow::<T>::get().into();
-
-			let vote = Vote::<T> {
-				document_id: document_id,
-				yes_votes: 0,
-				no_votes: 0,
-				start: now,
-				end: end,
-				status: VoteStatus::InProgress,
-			};
-
-			QualificationVotes::<T>::insert(uid.clone(),&vote);
-			QualificationVotesCount::<T>::put(uid.clone());
-			Self::deposit_event(Event::QualificationVotingStarted(uid));
-
-			document.status = DocumentStatus::UnderReview;
-			Documents::<T>::insert(document_id.clone(),document);
-			Self::deposit_event(Event::DocumentStatusUpdated(document_id,1));
+			Self::do_create_qualification_voting(document_id, document)?;
 			
+			Self::record_transaction(&who);
 			Ok(())
 		}
 
-		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(4,4))]
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(4 + ROUND_SCHEDULING_ATTEMPTS as u64,5))]
 		pub fn create_verification_voting(origin: OriginFor<T>, document_id: u64) -> DispatchResult{
 
 			let who = ensure_signed(origin)?;
 			ensure!(Self::ensure_qualifier(who.clone()) || Self::ensure_contributor(who.clone()),Error::<T>::NotAuthorized);
 
-			let mut document = Self::get_document(document_id.clone()).ok_or(Error::<T>::DocumentNotFound)?;
+			let document = Self::get_document(document_id.clone()).ok_or(Error::<T>::DocumentNotFound)?;
 
 			ensure!(document.status == DocumentStatus::SuccessfulReview, Error::<T>::DocumentNotReviewed);
+			ensure!(!Self::has_conflict(&who,&document),Error::<T>::ConflictOfInterest);
 
-			let uid = Self::get_verification_vote_count().checked_add(1).ok_or(ArithmeticError::Overflow)?;
-
-			let now = <frame_system::Pallet<T>>::block_number();
-
-			let end = now + VerificationVotingWindow::<T>::get().into();
-
-			let vote = Vote::<T> {
-				document_id: document_id,
-				yes_votes: 0,
-				no_votes: 0,
-				start: now,
-				end: end,
-				status: VoteStatus::InProgress,
-			};
-
-			VerificationVotes::<T>::insert(uid.clone(),&vote);
-			VerificationVotesCount::<T>::put(uid.clone());
-			Self::deposit_event(Event::VerificationVotingStarted(uid));
-
-			document.status = DocumentStatus::VoteInProgress;
-			Documents::<T>::insert(document_id.clone(),document);
-			Self::deposit_event(Event::DocumentStatusUpdated(document_id,3));
+			Self::do_create_verification_voting(document_id, document)?;
 			
+			Self::record_transaction(&who);
 			Ok(())
 		}
 
-		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(4,4))]
-		pub fn cast_qualification_vote(origin: OriginFor<T>, voting_id: u64, vote_cast: bool) -> DispatchResult  {
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(5,5))]
+		pub fn cast_qualification_vote(origin: OriginFor<T>, voting_id: u64, scorecard: Scorecard,
+		rationale: Option<Rationale>) -> DispatchResult  {
 			let who = ensure_signed(origin)?;
 			ensure!(Self::ensure_qualifier(who.clone()),Error::<T>::NotAQualifier);
+			ensure!(scorecard.scores().iter().all(|score| *score <= T::MaxScore::get()),Error::<T>::ScoreOutOfRange);
+			if let Some(note) = rationale.as_ref().and_then(|r| r.note.as_ref()) {
+				ensure!(!note.is_empty(),Error::<T>::RationaleNoteEmpty);
+				ensure!(note.len() as u32 <= T::MaxRationaleNoteLength::get(),Error::<T>::RationaleNoteTooLong);
+			}
 			let vote_type = VoteType::Qualification;
-			ensure!(!MemberVote::<T>::contains_key((who.clone(),vote_type.clone(),voting_id.clone())),Error::<T>::MemberAlreadyVoted);
+			ensure!(!MemberVotes::<T>::contains_key((vote_type.clone(),voting_id.clone(),who.clone())),Error::<T>::MemberAlreadyVoted);
 
 			let mut vote = Self::get_qualification_vote(voting_id.clone()).ok_or(Error::<T>::VoteNotFound)?;
 			ensure!(vote.status == VoteStatus::InProgress, Error::<T>::VoteNotInProgress);
 			let now = <frame_system::Pallet<T>>::block_number();
 			ensure!(now > vote.start && now < vote.end, Error::<T>::VotingWindowNotValid);
+			ensure!(vote.yes_votes + vote.no_votes < T::MaxVotesPerRound::get() as u64,Error::<T>::TooManyVotes);
+			let document = Self::get_document(vote.document_id.clone()).ok_or(Error::<T>::DocumentNotFound)?;
+			ensure!(!Self::has_conflict(&who,&document),Error::<T>::ConflictOfInterest);
+			ensure!(!Recusals::<T>::contains_key(vote.document_id.clone(),&who),Error::<T>::ConflictDeclared);
+			ensure!(!PendingPanels::<T>::contains_key(voting_id.clone()),Error::<T>::PanelNotDrawn);
+			if let Some(panel) = Self::get_review_panel(voting_id.clone()) {
+				ensure!(panel.contains(&who),Error::<T>::NotOnReviewPanel);
+			}
 
+			// The ballot counts as a yes when it meets every threshold on its own
+			let vote_cast = scorecard.meets(&ScoreThresholds::<T>::get());
 			if vote_cast {
 				vote.yes_votes = vote.yes_votes + 1;
 			} else {
@@ -494,9 +1349,21 @@ pub mod pallet {
 
 
 			QualificationVotes::<T>::insert(voting_id.clone(),&vote);
-			MemberVote::<T>::insert((who.clone(),vote_type.clone(),voting_id.clone()),vote_cast);
+			MemberVotes::<T>::insert((vote_type.clone(),voting_id.clone(),who.clone()),vote_cast);
+			Activities::<T>::mutate(&who,|activity| activity.votes_cast = activity.votes_cast.saturating_add(1));
+			ScoreTotals::<T>::mutate(voting_id.clone(),|(totals, ballots)| {
+				for (total, score) in totals.iter_mut().zip(scorecard.scores().iter()) {
+					*total = total.saturating_add(*score as u32);
+				}
+				*ballots = ballots.saturating_add(1);
+			});
+			Scorecards::<T>::insert(voting_id.clone(),who.clone(),scorecard);
+			if let Some(rationale) = rationale {
+				QualificationRationales::<T>::insert(voting_id.clone(),who.clone(),rationale);
+			}
 			Self::deposit_event(Event::VoteCast(0,voting_id));
 
+			Self::record_transaction(&who);
 			Ok(())
 		}
 
@@ -505,12 +1372,15 @@ pub mod pallet {
 			let who = ensure_signed(origin)?;
 			ensure!(Self::ensure_contributor(who.clone()),Error::<T>::NotAContributor);
 			let vote_type = VoteType::Verification;
-			ensure!(!MemberVote::<T>::contains_key((who.clone(),vote_type.clone(),voting_id.clone())),Error::<T>::MemberAlreadyVoted);
+			ensure!(!MemberVotes::<T>::contains_key((vote_type.clone(),voting_id.clone(),who.clone())),Error::<T>::MemberAlreadyVoted);
 
 			let mut vote = Self::get_verification_vote(voting_id.clone()).ok_or(Error::<T>::VoteNotFound)?;
 			ensure!(vote.status == VoteStatus::InProgress, Error::<T>::VoteNotInProgress);
 			let now = <frame_system::Pallet<T>>::block_number();
 			ensure!(now > vote.start && now < vote.end, Error::<T>::VotingWindowNotValid);
+			ensure!(vote.yes_votes + vote.no_votes < T::MaxVotesPerRound::get() as u64,Error::<T>::TooManyVotes);
+			let document = Self::get_document(vote.document_id.clone()).ok_or(Error::<T>::DocumentNotFound)?;
+			ensure!(!Self::has_conflict(&who,&document),Error::<T>::ConflictOfInterest);
 
 			if vote_cast {
 				vote.yes_votes = vote.yes_votes + 1;
@@ -519,111 +1389,257 @@ pub mod pallet {
 			}
 
 			VerificationVotes::<T>::insert(voting_id.clone(),&vote);
-			MemberVote::<T>::insert((who.clone(),vote_type.clone(),voting_id.clone()),vote_cast);
+			MemberVotes::<T>::insert((vote_type.clone(),voting_id.clone(),who.clone()),vote_cast);
+			Activities::<T>::mutate(&who,|activity| activity.votes_cast = activity.votes_cast.saturating_add(1));
 			Self::deposit_event(Event::VoteCast(1,voting_id));
 
+			Self::record_transaction(&who);
 			Ok(())
 		}
 
-		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(4,4))]
-		pub fn finalize_qualification_voting(origin: OriginFor<T>, voting_id: u64) -> DispatchResult {
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(5,5)
+			+ Pallet::<T>::finalize_weight(T::MaxVotesPerRound::get() as u64,T::MaxMembers::get()))]
+		pub fn finalize_qualification_voting(origin: OriginFor<T>, voting_id: u64) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
 			ensure!(Self::ensure_qualifier(who.clone()),Error::<T>::NotAQualifier);
 
-			let mut vote = Self::get_qualification_vote(voting_id.clone()).ok_or(Error::<T>::VoteNotFound)?;
-			ensure!(vote.status == VoteStatus::InProgress, Error::<T>::VoteNotInProgress);
-			let mut document = Self::get_document(vote.document_id.clone()).ok_or(Error::<T>::DocumentNotFound)?;
-			ensure!(document.status == DocumentStatus::UnderReview, Error::<T>::DocumentNotUnderReview);
-			let now = <frame_system::Pallet<T>>::block_number();
-			ensure!(now > vote.end,Error::<T>::VoteStillInProgress);
+			let vote = Self::get_qualification_vote(voting_id.clone()).ok_or(Error::<T>::VoteNotFound)?;
+			let document = Self::get_document(vote.document_id.clone()).ok_or(Error::<T>::DocumentNotFound)?;
+			ensure!(!Self::has_conflict(&who,&document),Error::<T>::ConflictOfInterest);
 
-			let quorum = QualificationQuorum::<T>::get().into();
-			let total_votes = vote.yes_votes + vote.no_votes;
+			let finalize_weight = Self::do_finalize_qualification_voting(voting_id.clone())?;
+			Self::cancel_round_finalization(VoteType::Qualification,voting_id);
 
-			if total_votes < quorum {
-				vote.status = VoteStatus::Failed;
-				document.status = DocumentStatus::Rejected;
-				QualificationVotes::<T>::insert(voting_id.clone(),&vote);
-				Documents::<T>::insert(vote.document_id.clone(),document);
-				Self::deposit_event(Event::DocumentStatusUpdated(vote.document_id,5));
-				Self::deposit_event(Event::QualificationVotingEnded(voting_id));
+			Self::record_transaction(&who);
+			Ok(Some(10_000 + T::DbWeight::get().reads_writes(5,5) + finalize_weight).into())
+		}
 
-				return Ok(());
-			}
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(5,5)
+			+ Pallet::<T>::finalize_weight(T::MaxVotesPerRound::get() as u64,T::MaxMembers::get()))]
+		pub fn finalize_verification_voting(origin: OriginFor<T>, voting_id: u64) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(Self::ensure_qualifier(who.clone()) || Self::ensure_contributor(who.clone()),Error::<T>::NotAuthorized);
 
-			match vote.yes_votes > vote.no_votes {
-				true => {
-					vote.status = VoteStatus::Passed;
-					document.status = DocumentStatus::SuccessfulReview;
-					QualificationVotes::<T>::insert(voting_id.clone(),&vote);
-					Documents::<T>::insert(vote.document_id.clone(),document);
-					Self::deposit_event(Event::DocumentStatusUpdated(vote.document_id,2));
-					Self::deposit_event(Event::QualificationVotingEnded(voting_id));
+			let vote = Self::get_verification_vote(voting_id.clone()).ok_or(Error::<T>::VoteNotFound)?;
+			let document = Self::get_document(vote.document_id.clone()).ok_or(Error::<T>::DocumentNotFound)?;
+			ensure!(!Self::has_conflict(&who,&document),Error::<T>::ConflictOfInterest);
+
+			let finalize_weight = Self::do_finalize_verification_voting(voting_id.clone())?;
+			Self::cancel_round_finalization(VoteType::Verification,voting_id);
+
+			Self::record_transaction(&who);
+			Ok(Some(10_000 + T::DbWeight::get().reads_writes(5,5) + finalize_weight).into())
+		}
+
+		/// Finalize a qualification or verification round once its window has closed. Every such
+		/// round schedules this call, with the root origin, for the block after it ends. The
+		/// weight of the round's ballots and review panel is refunded down to what it had.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(6,6)
+			+ Pallet::<T>::finalize_weight(T::MaxVotesPerRound::get() as u64,T::MaxMembers::get()))]
+		pub fn finalize_round(origin: OriginFor<T>, vote_type: VoteType, voting_id: u64) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+
+			let finalize_weight = match vote_type {
+				VoteType::Qualification => Self::do_finalize_qualification_voting(voting_id)?,
+				VoteType::Verification => Self::do_finalize_verification_voting(voting_id)?,
+				_ => return Err(Error::<T>::VoteNotFound.into()),
+			};
+
+			Ok(Some(10_000 + T::DbWeight::get().reads_writes(6,6) + finalize_weight).into())
+		}
+
+		/// Open a fresh voting round on a rejected document, at the stage where it was rejected.
+		/// Reserves `AppealDeposit` from the creator, which is returned if the appeal passes and
+		/// slashed otherwise. Appeal rounds need `AppealQuorumIncrease` more votes per attempt.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(7 + ROUND_SCHEDULING_ATTEMPTS as u64,7))]
+		#[transactional]
+		pub fn appeal_document(origin: OriginFor<T>, document_id: u64) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let document = Self::get_document(document_id.clone()).ok_or(Error::<T>::DocumentNotFound)?;
+			ensure!(document.creator == who,Error::<T>::NotDocumentCreator);
+			ensure!(document.status == DocumentStatus::Rejected || document.status == DocumentStatus::QuorumNotReached,
+				Error::<T>::DocumentNotRejected);
+
+			let appeals = Self::get_appeals(document_id.clone());
+			ensure!((appeals.len() as u32) < T::MaxAppeals::get(),Error::<T>::TooManyAppeals);
+			let attempt = appeals.len() as u32 + 1;
+
+			let vote_type = Self::get_document_rounds(document_id.clone())
+				.into_iter()
+				.rev()
+				.map(|(vote_type, _)| vote_type)
+				.find(|vote_type| *vote_type == VoteType::Qualification || *vote_type == VoteType::Verification)
+				.ok_or(Error::<T>::NoRoundToAppeal)?;
+
+			let deposit = T::AppealDeposit::get();
+			T::Currency::reserve(&who,deposit)?;
+			let prior_status = document.status.clone();
+
+			let increase = T::AppealQuorumIncrease::get().saturating_mul(attempt);
+			let voting_id = match vote_type {
+				VoteType::Qualification => {
+					let voting_id = Self::do_create_qualification_voting(document_id.clone(),document)?;
+					RoundQuorums::<T>::insert(vote_type,voting_id,QualificationQuorum::<T>::get().saturating_add(increase));
+					voting_id
 				},
-				false => {
-					vote.status = VoteStatus::Failed;
-					document.status = DocumentStatus::Rejected;
-					QualificationVotes::<T>::insert(voting_id.clone(),&vote);
-					Documents::<T>::insert(vote.document_id.clone(),document);
-					Self::deposit_event(Event::DocumentStatusUpdated(vote.document_id,5));
-					Self::deposit_event(Event::QualificationVotingEnded(voting_id));
+				_ => {
+					let voting_id = Self::do_create_verification_voting(document_id.clone(),document)?;
+					RoundQuorums::<T>::insert(vote_type,voting_id,VerificationQuorum::<T>::get().saturating_add(increase));
+					voting_id
 				},
+			};
+
+			let appeal = Appeal::<T> {
+				vote_type: vote_type,
+				voting_id: voting_id,
+				deposit: deposit,
+				outcome: None,
+				prior_status: prior_status,
+			};
+			Appeals::<T>::append(document_id.clone(),appeal);
+			Self::deposit_event(Event::DocumentAppealed(document_id,voting_id));
+
+			Self::record_transaction(&who);
+			Ok(())
+		}
+
+		/// Flag a verified document as forged, or as needing to be withdrawn for legal or
+		/// privacy reasons. The caller reserves `FlagDeposit`. Once `FlagThreshold` flags are
+		/// open, contributors vote on whether to revoke or quarantine the document.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(5,7))]
+		pub fn flag_document(origin: OriginFor<T>, document_id: u64, reason: FlagReason,
+		note: Option<Vec<u8>>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(Self::ensure_member(who.clone()),Error::<T>::NotAMember);
+			if let Some(note) = note.as_ref() {
+				ensure!(!note.is_empty(),Error::<T>::RationaleNoteEmpty);
+				ensure!(note.len() as u32 <= T::MaxRationaleNoteLength::get(),Error::<T>::RationaleNoteTooLong);
+			}
+			let document = Self::get_document(document_id.clone()).ok_or(Error::<T>::DocumentNotFound)?;
+			ensure!(document.status == DocumentStatus::Verified,Error::<T>::DocumentNotVerified);
+			ensure!(!ActiveReviews::<T>::contains_key(document_id.clone()),Error::<T>::ReviewInProgress);
+
+			let mut flags = Self::get_flags(document_id.clone());
+			ensure!(!flags.iter().any(|flag| flag.flagger == who && flag.review.is_none()),Error::<T>::AlreadyFlagged);
+
+			let open_flags = flags.iter().filter(|flag| flag.review.is_none()).count() as u32 + 1;
+			let review = if open_flags >= T::FlagThreshold::get() {
+				Some(Self::get_review_vote_count().checked_add(1).ok_or(ArithmeticError::Overflow)?)
+			} else {
+				None
+			};
+
+			let deposit = T::FlagDeposit::get();
+			T::Currency::reserve(&who,deposit)?;
+
+			let flag = Flag::<T> {
+				flagger: who.clone(),
+				reason: reason,
+				note: note,
+				deposit: deposit,
+				review: None,
+				outcome: None,
+			};
+			flags.push(flag);
+			Self::deposit_event(Event::DocumentFlagged(who,document_id,reason));
+
+			if let Some(voting_id) = review {
+				for flag in flags.iter_mut().filter(|flag| flag.review.is_none()) {
+					flag.review = Some(voting_id);
+				}
+				Self::do_create_review_voting(document_id.clone(),voting_id);
 			}
+			Flags::<T>::insert(document_id.clone(),flags);
 
+			Self::record_transaction(&who);
 			Ok(())
 		}
 
-		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(4,4))]
-		pub fn finalize_verification_voting(origin: OriginFor<T>, voting_id: u64) -> DispatchResult {
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(4,2))]
+		pub fn cast_review_vote(origin: OriginFor<T>, voting_id: u64, vote_cast: bool) -> DispatchResult  {
+			let who = ensure_signed(origin)?;
+			ensure!(Self::ensure_contributor(who.clone()),Error::<T>::NotAContributor);
+			let vote_type = VoteType::Review;
+			ensure!(!MemberVotes::<T>::contains_key((vote_type.clone(),voting_id.clone(),who.clone())),Error::<T>::MemberAlreadyVoted);
+
+			let mut vote = Self::get_review_vote(voting_id.clone()).ok_or(Error::<T>::VoteNotFound)?;
+			ensure!(vote.status == VoteStatus::InProgress, Error::<T>::VoteNotInProgress);
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(now > vote.start && now < vote.end, Error::<T>::VotingWindowNotValid);
+			ensure!(vote.yes_votes + vote.no_votes < T::MaxVotesPerRound::get() as u64,Error::<T>::TooManyVotes);
+			let document = Self::get_document(vote.document_id.clone()).ok_or(Error::<T>::DocumentNotFound)?;
+			ensure!(!Self::has_conflict(&who,&document),Error::<T>::ConflictOfInterest);
+
+			if vote_cast {
+				vote.yes_votes = vote.yes_votes + 1;
+			} else {
+				vote.no_votes = vote.no_votes + 1;
+			}
+
+			ReviewVotes::<T>::insert(voting_id.clone(),&vote);
+			MemberVotes::<T>::insert((vote_type.clone(),voting_id.clone(),who.clone()),vote_cast);
+			Activities::<T>::mutate(&who,|activity| activity.votes_cast = activity.votes_cast.saturating_add(1));
+			Self::deposit_event(Event::VoteCast(3,voting_id));
+
+			Self::record_transaction(&who);
+			Ok(())
+		}
+
+		/// Conclude a review vote. If it passes, the document is revoked when any of the flags
+		/// alleges forgery, and quarantined otherwise. If it fails on the votes, the flag
+		/// deposits are slashed.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(5,7)
+			+ Pallet::<T>::finalize_weight(T::MaxVotesPerRound::get() as u64,T::MaxMembers::get()))]
+		pub fn finalize_review_voting(origin: OriginFor<T>, voting_id: u64) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
 			ensure!(Self::ensure_qualifier(who.clone()) || Self::ensure_contributor(who.clone()),Error::<T>::NotAuthorized);
 
-			let mut vote = Self::get_verification_vote(voting_id.clone()).ok_or(Error::<T>::VoteNotFound)?;
+			let mut vote = Self::get_review_vote(voting_id.clone()).ok_or(Error::<T>::VoteNotFound)?;
 			ensure!(vote.status == VoteStatus::InProgress, Error::<T>::VoteNotInProgress);
 			let mut document = Self::get_document(vote.document_id.clone()).ok_or(Error::<T>::DocumentNotFound)?;
-			ensure!(document.status == DocumentStatus::VoteInProgress, Error::<T>::IncorrectDocumentStatus);
+			ensure!(!Self::has_conflict(&who,&document),Error::<T>::ConflictOfInterest);
 			let now = <frame_system::Pallet<T>>::block_number();
 			ensure!(now > vote.end,Error::<T>::VoteStillInProgress);
 
 			let quorum = VerificationQuorum::<T>::get().into();
 			let total_votes = vote.yes_votes + vote.no_votes;
 
-			if total_votes < quorum {
+			let slash = if total_votes < quorum {
 				vote.status = VoteStatus::Failed;
-				document.status = DocumentStatus::Rejected;
-				VerificationVotes::<T>::insert(voting_id.clone(),&vote);
-				Documents::<T>::insert(vote.document_id.clone(),document);
-				Self::deposit_event(Event::DocumentStatusUpdated(vote.document_id,5));
-				Self::deposit_event(Event::VerificationVotingEnded(voting_id));
+				false
+			} else if vote.yes_votes > vote.no_votes {
+				vote.status = VoteStatus::Passed;
+				let forged = Self::get_flags(vote.document_id.clone())
+					.iter()
+					.any(|flag| flag.review == Some(voting_id) && flag.reason == FlagReason::Forged);
+				document.status = if forged { DocumentStatus::Revoked } else { DocumentStatus::Quarantined };
+				false
+			} else {
+				vote.status = VoteStatus::Failed;
+				true
+			};
 
-				return Ok(());
+			ReviewVotes::<T>::insert(voting_id.clone(),&vote);
+			Self::record_round_summary(VoteType::Review,voting_id.clone(),&vote);
+			if total_votes >= quorum {
+				Self::record_winning_votes(VoteType::Review,voting_id.clone(),vote.status == VoteStatus::Passed);
 			}
-
-			match vote.yes_votes > vote.no_votes {
-				true => {
-					vote.status = VoteStatus::Passed;
-					document.status = DocumentStatus::Verified;
-					VerificationVotes::<T>::insert(voting_id.clone(),&vote);
-					Documents::<T>::insert(vote.document_id.clone(),document);
-					Self::deposit_event(Event::DocumentStatusUpdated(vote.document_id,4));
-					Self::deposit_event(Event::VerificationVotingEnded(voting_id));
-				},
-				false => {
-					vote.status = VoteStatus::Failed;
-					document.status = DocumentStatus::Rejected;
-					VerificationVotes::<T>::insert(voting_id.clone(),&vote);
-					Documents::<T>::insert(vote.document_id.clone(),document);
-					Self::deposit_event(Event::DocumentStatusUpdated(vote.document_id,5));
-					Self::deposit_event(Event::VerificationVotingEnded(voting_id));
-				},
+			Self::conclude_review(vote.document_id.clone(),voting_id.clone(),vote.status,slash);
+			if vote.status == VoteStatus::Passed {
+				Documents::<T>::insert(vote.document_id.clone(),&document);
+				Self::deposit_event(Event::DocumentStatusUpdated(vote.document_id,document.status as u8));
 			}
+			Self::deposit_event(Event::ReviewVotingEnded(voting_id));
 
-			Ok(())
+			Self::record_transaction(&who);
+			let finalize_weight = Self::finalize_weight(total_votes,0);
+			Ok(Some(10_000 + T::DbWeight::get().reads_writes(5,7) + finalize_weight).into())
 		}
 
 		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1))]
 		pub fn set_qualification_voting_window(origin: OriginFor<T>, window: u32) -> DispatchResult {
-			ensure_root(origin)?;
+			Self::ensure_admin(origin)?;
 			ensure!(window > 0, Error::<T>::VotingWindowNotValid);
 
 			QualificationVotingWindow::<T>::put(window.clone());
@@ -635,7 +1651,7 @@ pub mod pallet {
 
 		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1))]
 		pub fn set_verification_voting_window(origin: OriginFor<T>, window: u32) -> DispatchResult {
-			ensure_root(origin)?;
+			Self::ensure_admin(origin)?;
 			ensure!(window > 0, Error::<T>::VotingWindowNotValid);
 
 			VerificationVotingWindow::<T>::put(window.clone());
@@ -647,7 +1663,7 @@ pub mod pallet {
 
 		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1))]
 		pub fn set_qualification_quorum(origin: OriginFor<T>, quorum: u32) -> DispatchResult {
-			ensure_root(origin)?;
+			Self::ensure_admin(origin)?;
 
 			QualificationQuorum::<T>::put(quorum.clone());
 
@@ -656,9 +1672,35 @@ pub mod pallet {
 			Ok(())
 		}
 
+		/// Set the minimum mean score, per criterion, a document needs to pass qualification.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1))]
+		pub fn set_score_thresholds(origin: OriginFor<T>, thresholds: Scorecard) -> DispatchResult {
+			Self::ensure_admin(origin)?;
+			ensure!(thresholds.scores().iter().all(|score| *score <= T::MaxScore::get()),Error::<T>::ScoreOutOfRange);
+
+			ScoreThresholds::<T>::put(thresholds.clone());
+
+			Self::deposit_event(Event::ScoreThresholdsChanged(thresholds));
+
+			Ok(())
+		}
+
+		/// Set how many qualifiers are drawn to review each new qualification round. Zero lets
+		/// every qualifier vote.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1))]
+		pub fn set_review_panel_size(origin: OriginFor<T>, size: u32) -> DispatchResult {
+			Self::ensure_admin(origin)?;
+
+			ReviewPanelSize::<T>::put(size.clone());
+
+			Self::deposit_event(Event::ReviewPanelSizeChanged(size));
+
+			Ok(())
+		}
+
 		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1))]
 		pub fn set_verification_quorum(origin: OriginFor<T>, quorum: u32) -> DispatchResult {
-			ensure_root(origin)?;
+			Self::ensure_admin(origin)?;
 
 			VerificationQuorum::<T>::put(quorum.clone());
 
@@ -667,10 +1709,449 @@ pub mod pallet {
 			Ok(())
 		}
 
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(5,4))]
+		pub fn submit_proposal(origin: OriginFor<T>, call: Box<<T as Config>::Proposal>,
+		enactment_delay: T::BlockNumber) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(Self::ensure_member(who.clone()),Error::<T>::NotAMember);
+
+			let uid = Self::get_proposal_count().checked_add(1).ok_or(ArithmeticError::Overflow)?;
+
+			let now = <frame_system::Pallet<T>>::block_number();
+
+			let end = now + ProposalVotingWindow::<T>::get().into();
+
+			let proposal = DaoProposal::<T> {
+				proposer: who.clone(),
+				call: *call,
+				enactment_delay: enactment_delay,
+			};
+
+			let vote = Vote::<T> {
+				document_id: uid,
+				yes_votes: 0,
+				no_votes: 0,
+				start: now,
+				end: end,
+				status: VoteStatus::InProgress,
+			};
+
+			Proposals::<T>::insert(uid.clone(),proposal);
+			ProposalVotes::<T>::insert(uid.clone(),&vote);
+			ProposalsCount::<T>::put(uid.clone());
+			ExpiringRounds::<T>::append(Self::expiry_block(end),(VoteType::Proposal,uid.clone()));
+			Self::deposit_event(Event::ProposalSubmitted(who,uid));
+
+			Self::record_transaction(&who);
+			Ok(())
+		}
+
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(5,2))]
+		pub fn cast_proposal_vote(origin: OriginFor<T>, proposal_id: u64, vote_cast: bool) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(Self::ensure_member(who.clone()),Error::<T>::NotAMember);
+			let vote_type = VoteType::Proposal;
+			ensure!(!MemberVotes::<T>::contains_key((vote_type.clone(),proposal_id.clone(),who.clone())),Error::<T>::MemberAlreadyVoted);
+
+			let mut vote = Self::get_proposal_vote(proposal_id.clone()).ok_or(Error::<T>::VoteNotFound)?;
+			ensure!(vote.status == VoteStatus::InProgress, Error::<T>::VoteNotInProgress);
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(now > vote.start && now < vote.end, Error::<T>::VotingWindowNotValid);
+			ensure!(vote.yes_votes + vote.no_votes < T::MaxVotesPerRound::get() as u64,Error::<T>::TooManyVotes);
+
+			if vote_cast {
+				vote.yes_votes = vote.yes_votes + 1;
+			} else {
+				vote.no_votes = vote.no_votes + 1;
+			}
+
+			ProposalVotes::<T>::insert(proposal_id.clone(),&vote);
+			MemberVotes::<T>::insert((vote_type.clone(),proposal_id.clone(),who.clone()),vote_cast);
+			Activities::<T>::mutate(&who,|activity| activity.votes_cast = activity.votes_cast.saturating_add(1));
+			Self::deposit_event(Event::VoteCast(2,proposal_id));
+
+			Self::record_transaction(&who);
+			Ok(())
+		}
+
+		/// Close the vote on a proposal. A passing proposal is executed right away, with at most
+		/// `weight_bound` weight, or scheduled if it was submitted with an enactment delay. Only
+		/// the weight of an executed call, and of the ballots cast, is charged on top of the vote's
+		/// own.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(4,4)
+			+ Pallet::<T>::finalize_weight(T::MaxVotesPerRound::get() as u64,0) + *weight_bound)]
+		pub fn finalize_proposal_voting(origin: OriginFor<T>, proposal_id: u64, weight_bound: Weight) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(Self::ensure_member(who.clone()),Error::<T>::NotAMember);
+
+			let mut vote = Self::get_proposal_vote(proposal_id.clone()).ok_or(Error::<T>::VoteNotFound)?;
+			ensure!(vote.status == VoteStatus::InProgress, Error::<T>::VoteNotInProgress);
+			let proposal = Self::get_proposal(proposal_id.clone()).ok_or(Error::<T>::ProposalNotFound)?;
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(now > vote.end,Error::<T>::VoteStillInProgress);
+
+			let quorum = ProposalQuorum::<T>::get().into();
+			let total_votes = vote.yes_votes + vote.no_votes;
+			let approval = Perbill::from_rational(vote.yes_votes, total_votes.max(1));
+			let base_weight = 10_000 + T::DbWeight::get().reads_writes(4,4) + Self::finalize_weight(total_votes,0);
+
+			if total_votes < quorum || approval <= ProposalThreshold::<T>::get() {
+				vote.status = VoteStatus::Failed;
+				ProposalVotes::<T>::insert(proposal_id.clone(),&vote);
+				Self::record_round_summary(VoteType::Proposal,proposal_id.clone(),&vote);
+				Proposals::<T>::remove(proposal_id.clone());
+				if total_votes >= quorum {
+					Self::record_winning_votes(VoteType::Proposal,proposal_id.clone(),false);
+				}
+				Self::deposit_event(Event::ProposalVotingEnded(proposal_id,vote.status));
+				Self::record_transaction(&who);
+
+				return Ok(Some(base_weight).into());
+			}
+
+			let mut call_weight = 0;
+			if proposal.enactment_delay.is_zero() {
+				let info = proposal.call.get_dispatch_info();
+				ensure!(info.weight <= weight_bound,Error::<T>::ProposalWeightTooHigh);
+
+				vote.status = VoteStatus::Passed;
+				ProposalVotes::<T>::insert(proposal_id.clone(),&vote);
+				Self::record_round_summary(VoteType::Proposal,proposal_id.clone(),&vote);
+				Self::record_winning_votes(VoteType::Proposal,proposal_id.clone(),true);
+				Proposals::<T>::remove(proposal_id.clone());
+				Self::deposit_event(Event::ProposalVotingEnded(proposal_id,vote.status));
+
+				let result = proposal.call.dispatch(RawOrigin::Dao.into());
+				call_weight = extract_actual_weight(&result,&info);
+				Self::deposit_event(Event::ProposalExecuted(proposal_id,result.map(|_| ()).map_err(|e| e.error)));
+			} else {
+				let when = now + proposal.enactment_delay;
+				T::Scheduler::schedule_named(
+					(PROPOSAL_TASK_ID,proposal_id).encode(),
+					DispatchTime::At(when),
+					None,
+					SCHEDULE_PRIORITY,
+					RawOrigin::Dao.into(),
+					proposal.call,
+				).map_err(|_| Error::<T>::ProposalSchedulingFailed)?;
+
+				vote.status = VoteStatus::Passed;
+				ProposalVotes::<T>::insert(proposal_id.clone(),&vote);
+				Self::record_round_summary(VoteType::Proposal,proposal_id.clone(),&vote);
+				Self::record_winning_votes(VoteType::Proposal,proposal_id.clone(),true);
+				Proposals::<T>::remove(proposal_id.clone());
+				Self::deposit_event(Event::ProposalVotingEnded(proposal_id,vote.status));
+				Self::deposit_event(Event::ProposalScheduled(proposal_id,when));
+			}
+
+			Self::record_transaction(&who);
+			Ok(Some(base_weight + call_weight).into())
+		}
+
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1))]
+		pub fn set_proposal_voting_window(origin: OriginFor<T>, window: u32) -> DispatchResult {
+			Self::ensure_admin(origin)?;
+			ensure!(window > 0, Error::<T>::VotingWindowNotValid);
+
+			ProposalVotingWindow::<T>::put(window.clone());
+
+			Self::deposit_event(Event::ProposalVotingWindowChanged(window));
+
+			Ok(())
+		}
+
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1))]
+		pub fn set_proposal_quorum(origin: OriginFor<T>, quorum: u32) -> DispatchResult {
+			Self::ensure_admin(origin)?;
+
+			ProposalQuorum::<T>::put(quorum.clone());
+
+			Self::deposit_event(Event::ProposalQuorumChanged(quorum));
+
+			Ok(())
+		}
+
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1))]
+		pub fn set_proposal_threshold(origin: OriginFor<T>, threshold: Perbill) -> DispatchResult {
+			Self::ensure_admin(origin)?;
+
+			ProposalThreshold::<T>::put(threshold.clone());
+
+			Self::deposit_event(Event::ProposalThresholdChanged(threshold));
+
+			Ok(())
+		}
+
+		/// Expire a round nobody finalized within `ExpiryGracePeriod` blocks of its end. The
+		/// document returns to the state it had before the round, so a new round can be opened.
+		/// Anyone can call this; `on_initialize` also does it automatically.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3,5))]
+		pub fn expire_vote(origin: OriginFor<T>, vote_type: VoteType, voting_id: u64) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			Self::do_expire_vote(vote_type, voting_id)?;
+			Self::record_transaction(&who);
+			Ok(())
+		}
+
+		/// Draw the review panel of a qualification round, `PanelDrawDelay` blocks after it opened,
+		/// so the draw is seeded with randomness nobody knew then. Voting opens with the draw. Every
+		/// such round schedules this call, with the root origin, and anyone can also make it. If too
+		/// few qualifiers are free by then, the round expires and a new one can be opened.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(6 + 4 * T::MaxMembers::get() as u64,8 + T::MaxMembers::get() as u64))]
+		#[transactional]
+		pub fn draw_review_panel(origin: OriginFor<T>, voting_id: u64) -> DispatchResult {
+			let who = ensure_signed(origin.clone()).ok();
+			if who.is_none() {
+				ensure_root(origin)?;
+			}
+
+			let size = Self::get_pending_panel(voting_id.clone()).ok_or(Error::<T>::NoPendingPanel)?;
+			let vote = Self::get_qualification_vote(voting_id.clone()).ok_or(Error::<T>::VoteNotFound)?;
+			ensure!(vote.status == VoteStatus::InProgress, Error::<T>::VoteNotInProgress);
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(now >= vote.start,Error::<T>::PanelNotDue);
+			let document = Self::get_document(vote.document_id.clone()).ok_or(Error::<T>::DocumentNotFound)?;
+
+			PendingPanels::<T>::remove(voting_id.clone());
+			let _ = T::Scheduler::cancel_named(Self::panel_task_id(voting_id.clone()));
+			match Self::do_draw_review_panel(voting_id.clone(), vote.document_id.clone(), &document, size) {
+				Ok(panel) => {
+					for member in panel.iter() {
+						Workloads::<T>::mutate(member, |load| *load = load.saturating_add(1));
+					}
+					ReviewPanels::<T>::insert(voting_id.clone(),&panel);
+					Self::deposit_event(Event::ReviewPanelDrawn(voting_id,panel));
+				},
+				Err(_) => Self::expire_round(VoteType::Qualification,voting_id,vote)?,
+			}
+
+			if let Some(who) = who {
+				Self::record_transaction(&who);
+			}
+			Ok(())
+		}
+
+		/// Recuse the calling qualifier from reviewing a document. They can no longer vote on its
+		/// qualification rounds, and each recusal lowers the quorum of those rounds by one.
+		/// Qualifiers with a conflict on the document cannot vote on it already.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(5,2))]
+		pub fn declare_conflict(origin: OriginFor<T>, document_id: u64) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(Self::ensure_qualifier(who.clone()),Error::<T>::NotAQualifier);
+			let document = Self::get_document(document_id.clone()).ok_or(Error::<T>::DocumentNotFound)?;
+			ensure!(!Self::has_conflict(&who,&document),Error::<T>::ConflictOfInterest);
+			ensure!(!Recusals::<T>::contains_key(document_id.clone(),&who),Error::<T>::ConflictDeclared);
+
+			let voted = Self::get_document_rounds(document_id.clone())
+				.into_iter()
+				.filter(|(vote_type, _)| *vote_type == VoteType::Qualification)
+				.any(|(vote_type, voting_id)| {
+					let in_progress = Self::get_vote(vote_type, voting_id)
+						.map_or(false, |vote| vote.status == VoteStatus::InProgress);
+					in_progress && MemberVotes::<T>::contains_key((vote_type,voting_id,who.clone()))
+				});
+			ensure!(!voted,Error::<T>::MemberAlreadyVoted);
+
+			Recusals::<T>::insert(document_id.clone(),who.clone(),());
+			RecusalCounts::<T>::mutate(document_id.clone(),|count| *count = count.saturating_add(1));
+			Self::deposit_event(Event::ConflictDeclared(who,document_id));
+
+			Self::record_transaction(&who);
+			Ok(())
+		}
+
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3,3))]
+		pub fn create_exhibition(origin: OriginFor<T>, name: Vec<u8>, note: Vec<u8>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(Self::ensure_collector(who.clone()),Error::<T>::NotACollector);
+			ensure!(!name.is_empty(),Error::<T>::ExhibitionNameNotProvided);
+
+			let uid = Self::get_exhibitions_count().checked_add(1).ok_or(ArithmeticError::Overflow)?;
+
+			let exhibition = Exhibition::<T> {
+				curator: who.clone(),
+				name: name,
+				note: note,
+				cover: None,
+				exhibits: Vec::new(),
+				published: false,
+			};
+
+			Exhibitions::<T>::insert(uid.clone(),exhibition);
+			ExhibitionsCount::<T>::put(uid.clone());
+			CuratorExhibitions::<T>::append(who.clone(),uid.clone());
+			Self::deposit_event(Event::ExhibitionCreated(who,uid));
+
+			Self::record_transaction(&who);
+			Ok(())
+		}
+
+		/// Change the name, curator note and cover of an exhibition. The cover must be one of its
+		/// exhibits.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2,1))]
+		pub fn update_exhibition(origin: OriginFor<T>, exhibition_id: u64, name: Vec<u8>, note: Vec<u8>,
+		cover: Option<u64>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let mut exhibition = Self::get_curated_exhibition(who, exhibition_id.clone())?;
+			ensure!(!name.is_empty(),Error::<T>::ExhibitionNameNotProvided);
+			if let Some(document_id) = cover {
+				ensure!(exhibition.exhibits.iter().any(|e| e.document_id == document_id),Error::<T>::CoverNotAnExhibit);
+			}
+
+			exhibition.name = name;
+			exhibition.note = note;
+			exhibition.cover = cover;
+
+			Exhibitions::<T>::insert(exhibition_id.clone(),exhibition);
+			Self::deposit_event(Event::ExhibitionUpdated(exhibition_id));
+
+			Self::record_transaction(&who);
+			Ok(())
+		}
+
+		/// Add a verified document at `position` in the exhibition, or at the end if `position` is
+		/// past the last exhibit.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3,1))]
+		pub fn add_exhibit(origin: OriginFor<T>, exhibition_id: u64, document_id: u64, annotation: Vec<u8>,
+		position: u32) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let mut exhibition = Self::get_curated_exhibition(who, exhibition_id.clone())?;
+			let document = Self::get_document(document_id.clone()).ok_or(Error::<T>::DocumentNotFound)?;
+			ensure!(document.status == DocumentStatus::Verified,Error::<T>::DocumentNotVerified);
+			ensure!(!exhibition.exhibits.iter().any(|e| e.document_id == document_id),Error::<T>::ExhibitAlreadyAdded);
+			ensure!((exhibition.exhibits.len() as u32) < T::MaxExhibits::get(),Error::<T>::TooManyExhibits);
+
+			let index = (position as usize).min(exhibition.exhibits.len());
+			exhibition.exhibits.insert(index, Exhibit { document_id: document_id, annotation: annotation });
+
+			Exhibitions::<T>::insert(exhibition_id.clone(),exhibition);
+			Self::deposit_event(Event::ExhibitionUpdated(exhibition_id));
+
+			Self::record_transaction(&who);
+			Ok(())
+		}
+
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2,1))]
+		pub fn remove_exhibit(origin: OriginFor<T>, exhibition_id: u64, document_id: u64) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let mut exhibition = Self::get_curated_exhibition(who, exhibition_id.clone())?;
+			let index = exhibition.exhibits.iter().position(|e| e.document_id == document_id).ok_or(Error::<T>::ExhibitNotFound)?;
+
+			exhibition.exhibits.remove(index);
+			if exhibition.cover == Some(document_id) {
+				exhibition.cover = None;
+			}
+
+			Exhibitions::<T>::insert(exhibition_id.clone(),exhibition);
+			Self::deposit_event(Event::ExhibitionUpdated(exhibition_id));
+
+			Self::record_transaction(&who);
+			Ok(())
+		}
+
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2,1))]
+		pub fn move_exhibit(origin: OriginFor<T>, exhibition_id: u64, document_id: u64, position: u32) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let mut exhibition = Self::get_curated_exhibition(who, exhibition_id.clone())?;
+			let index = exhibition.exhibits.iter().position(|e| e.document_id == document_id).ok_or(Error::<T>::ExhibitNotFound)?;
+
+			let exhibit = exhibition.exhibits.remove(index);
+			let index = (position as usize).min(exhibition.exhibits.len());
+			exhibition.exhibits.insert(index, exhibit);
+
+			Exhibitions::<T>::insert(exhibition_id.clone(),exhibition);
+			Self::deposit_event(Event::ExhibitionUpdated(exhibition_id));
+
+			Self::record_transaction(&who);
+			Ok(())
+		}
+
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2,1))]
+		pub fn set_exhibition_published(origin: OriginFor<T>, exhibition_id: u64, published: bool) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let mut exhibition = Self::get_curated_exhibition(who, exhibition_id.clone())?;
+
+			exhibition.published = published;
+
+			Exhibitions::<T>::insert(exhibition_id.clone(),exhibition);
+			Self::deposit_event(Event::ExhibitionPublished(exhibition_id,published));
+
+			Self::record_transaction(&who);
+			Ok(())
+		}
+
 	}
 
 	// Helpful functions
 	impl<T: Config> Pallet<T> {
+		fn get_roster(role: Roles) -> Vec<T::AccountId> {
+			match role {
+				Roles::QualifierRole => Qualifiers::<T>::get(),
+				Roles::CollectorRole => Collectors::<T>::get(),
+				Roles::ContributorRole => Contributors::<T>::get(),
+			}
+		}
+
+		/// Weight of concluding a round with `voters` ballots and a review panel of `members`, on
+		/// top of the call's own. Each panelist is checked for a recusal, and each ballot on the
+		/// winning side is credited to its voter.
+		pub fn finalize_weight(voters: u64, members: u32) -> Weight {
+			T::DbWeight::get().reads(members as u64)
+				.saturating_add(T::DbWeight::get().reads_writes(voters,voters))
+		}
+
+		/// Store the members of a role. The role's count is the last uid handed out, not the
+		/// roster length, so it is left alone.
+		fn put_roster(role: Roles, members: Vec<T::AccountId>) {
+			match role {
+				Roles::QualifierRole => Qualifiers::<T>::put(members),
+				Roles::CollectorRole => Collectors::<T>::put(members),
+				Roles::ContributorRole => Contributors::<T>::put(members),
+			}
+		}
+
+		/// Hand out the next uid of a role. Uids only ever increase, so none is reused after a
+		/// member leaves the role.
+		fn next_uid(role: Roles) -> Result<u32, DispatchError> {
+			let count = match role {
+				Roles::QualifierRole => Self::qualifiers_uid_count(),
+				Roles::CollectorRole => Self::collectors_uid_count(),
+				Roles::ContributorRole => Self::contributors_uid_count(),
+			};
+			let uid = count.checked_add(1).ok_or(ArithmeticError::Overflow)?;
+			match role {
+				Roles::QualifierRole => QualifiersCount::<T>::put(uid),
+				Roles::CollectorRole => CollectorsCount::<T>::put(uid),
+				Roles::ContributorRole => ContributorsCount::<T>::put(uid),
+			}
+			Ok(uid)
+		}
+
+		fn member_exists_error(role: Roles) -> Error<T> {
+			match role {
+				Roles::QualifierRole => Error::<T>::QualifierAlreadyExists,
+				Roles::CollectorRole => Error::<T>::CollectorAlreadyExists,
+				Roles::ContributorRole => Error::<T>::ContributorAlreadyExists,
+			}
+		}
+
+		fn not_a_member_error(role: Roles) -> Error<T> {
+			match role {
+				Roles::QualifierRole => Error::<T>::NotAQualifier,
+				Roles::CollectorRole => Error::<T>::NotACollector,
+				Roles::ContributorRole => Error::<T>::NotAContributor,
+			}
+		}
+
+		/// Accept the `AdminOrigin` as well as the `GovernanceOrigin`.
+		fn ensure_admin(origin: OriginFor<T>) -> DispatchResult {
+			match T::AdminOrigin::try_origin(origin) {
+				Ok(_) => Ok(()),
+				Err(origin) => T::GovernanceOrigin::ensure_origin(origin).map(|_| ()).map_err(Into::into),
+			}
+		}
+
 		pub fn ensure_contributor(who: T::AccountId) -> bool {
 
 			//let val = pallet_nft::Pallet::<T>::get_token((who.clone(),Roles::QualifierRole as u32));
@@ -679,7 +2160,8 @@ pub mod pallet {
 			let check: bool;
 
 			match contributors.binary_search(&who) {
-				Ok(_) => check = true,
+				// Members act through their badge, so a frozen or burned one suspends them
+				Ok(_) => check = pallet_nft::Pallet::<T>::holds_unfrozen_token(Roles::ContributorRole as u32,&who),
 				Err(_index) => check = false,
 			}
 			
@@ -692,7 +2174,8 @@ pub mod pallet {
 			let check: bool;
 
 			match collectors.binary_search(&who) {
-				Ok(_) => check = true,
+				// Members act through their badge, so a frozen or burned one suspends them
+				Ok(_) => check = pallet_nft::Pallet::<T>::holds_unfrozen_token(Roles::CollectorRole as u32,&who),
 				Err(_index) => check = false,
 			}
 			
@@ -705,13 +2188,552 @@ pub mod pallet {
 			let check: bool;
 
 			match qualifiers.binary_search(&who) {
-				Ok(_) => check = true,
+				// Members act through their badge, so a frozen or burned one suspends them
+				Ok(_) => check = pallet_nft::Pallet::<T>::holds_unfrozen_token(Roles::QualifierRole as u32,&who),
 				Err(_index) => check = false,
 			}
 			
 			check
 		}
 
+		pub fn do_create_qualification_voting(document_id: u64, mut document: Document<T>) -> Result<u64, DispatchError> {
+			let uid = Self::get_qualification_vote_count().checked_add(1).ok_or(ArithmeticError::Overflow)?;
+
+			let now = <frame_system::Pallet<T>>::block_number();
+
+			// A round with a review panel opens for voting once the panel is drawn
+			let panel_size = ReviewPanelSize::<T>::get();
+			let start = if panel_size > 0 {
+				let candidates = Self::panel_candidates(document_id, &document);
+				ensure!(candidates.len() >= panel_size as usize,Error::<T>::NotEnoughReviewers);
+				now.saturating_add(T::PanelDrawDelay::get())
+			} else {
+				now
+			};
+
+			let end = start + QualificationVotingWindow::<T>::get().into();
+			Self::schedule_round_finalization(VoteType::Qualification,uid,end);
+
+			let vote = Vote::<T> {
+				document_id: document_id,
+				yes_votes: 0,
+				no_votes: 0,
+				start: start,
+				end: end,
+				status: VoteStatus::InProgress,
+			};
+
+			QualificationVotes::<T>::insert(uid.clone(),&vote);
+			QualificationVotesCount::<T>::put(uid.clone());
+			ExpiringRounds::<T>::append(Self::expiry_block(end),(VoteType::Qualification,uid.clone()));
+			if panel_size > 0 {
+				PendingPanels::<T>::insert(uid.clone(),panel_size);
+				Self::schedule_panel_draw(uid.clone(),start);
+			}
+			DocumentRounds::<T>::append(document_id.clone(),(VoteType::Qualification,uid.clone()));
+			Self::deposit_event(Event::QualificationVotingStarted(uid));
+
+			document.status = DocumentStatus::UnderReview;
+			Documents::<T>::insert(document_id.clone(),document);
+			Self::deposit_event(Event::DocumentStatusUpdated(document_id,1));
+
+			Ok(uid)
+		}
+
+		pub fn do_create_verification_voting(document_id: u64, mut document: Document<T>) -> Result<u64, DispatchError> {
+			let uid = Self::get_verification_vote_count().checked_add(1).ok_or(ArithmeticError::Overflow)?;
+
+			let now = <frame_system::Pallet<T>>::block_number();
+
+			let end = now + VerificationVotingWindow::<T>::get().into();
+			Self::schedule_round_finalization(VoteType::Verification,uid,end);
+
+			let vote = Vote::<T> {
+				document_id: document_id,
+				yes_votes: 0,
+				no_votes: 0,
+				start: now,
+				end: end,
+				status: VoteStatus::InProgress,
+			};
+
+			VerificationVotes::<T>::insert(uid.clone(),&vote);
+			VerificationVotesCount::<T>::put(uid.clone());
+			ExpiringRounds::<T>::append(Self::expiry_block(end),(VoteType::Verification,uid.clone()));
+			DocumentRounds::<T>::append(document_id.clone(),(VoteType::Verification,uid.clone()));
+			Self::deposit_event(Event::VerificationVotingStarted(uid));
+
+			document.status = DocumentStatus::VoteInProgress;
+			Documents::<T>::insert(document_id.clone(),document);
+			Self::deposit_event(Event::DocumentStatusUpdated(document_id,3));
+
+			Ok(uid)
+		}
+
+		/// Conclude a qualification round, returning the weight that depends on its ballots and
+		/// review panel.
+		fn do_finalize_qualification_voting(voting_id: u64) -> Result<Weight, DispatchError> {
+			let mut vote = Self::get_qualification_vote(voting_id.clone()).ok_or(Error::<T>::VoteNotFound)?;
+			ensure!(vote.status == VoteStatus::InProgress, Error::<T>::VoteNotInProgress);
+			let mut document = Self::get_document(vote.document_id.clone()).ok_or(Error::<T>::DocumentNotFound)?;
+			ensure!(document.status == DocumentStatus::UnderReview, Error::<T>::DocumentNotUnderReview);
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(now > vote.end,Error::<T>::VoteStillInProgress);
+
+			// Recused qualifiers leave the electorate, and each lowers the quorum by one
+			let (recusals, members) = match Self::get_review_panel(voting_id.clone()) {
+				Some(panel) => (
+					panel
+						.iter()
+						.filter(|member| Recusals::<T>::contains_key(vote.document_id.clone(), *member))
+						.count() as u32,
+					panel.len() as u32,
+				),
+				None => (RecusalCounts::<T>::get(vote.document_id.clone()), 0),
+			};
+			let quorum = Self::get_round_quorum(VoteType::Qualification,voting_id.clone())
+				.unwrap_or(QualificationQuorum::<T>::get())
+				.saturating_sub(recusals).into();
+			let total_votes = vote.yes_votes + vote.no_votes;
+			document.scores = Self::mean_scores(voting_id.clone());
+
+			if total_votes < quorum {
+				vote.status = VoteStatus::Failed;
+				document.status = DocumentStatus::QuorumNotReached;
+			} else if document.scores.map_or(false, |scores| scores.meets(&ScoreThresholds::<T>::get())) {
+				vote.status = VoteStatus::Passed;
+				document.status = DocumentStatus::SuccessfulReview;
+			} else {
+				vote.status = VoteStatus::Failed;
+				document.status = DocumentStatus::Rejected;
+			}
+
+			QualificationVotes::<T>::insert(voting_id.clone(),&vote);
+			Self::record_round_summary(VoteType::Qualification,voting_id.clone(),&vote);
+			if total_votes >= quorum {
+				Self::record_winning_votes(VoteType::Qualification,voting_id.clone(),vote.status == VoteStatus::Passed);
+			}
+			Self::release_review_panel(voting_id.clone());
+			Documents::<T>::insert(vote.document_id.clone(),&document);
+			Self::conclude_appeal(vote.document_id.clone(),VoteType::Qualification,voting_id.clone(),vote.status,&document.creator);
+			Self::deposit_event(Event::DocumentStatusUpdated(vote.document_id,document.status as u8));
+			Self::deposit_event(Event::QualificationVotingEnded(voting_id));
+
+			Ok(Self::finalize_weight(total_votes,members))
+		}
+
+		/// Conclude a verification round, returning the weight that depends on its ballots.
+		fn do_finalize_verification_voting(voting_id: u64) -> Result<Weight, DispatchError> {
+			let mut vote = Self::get_verification_vote(voting_id.clone()).ok_or(Error::<T>::VoteNotFound)?;
+			ensure!(vote.status == VoteStatus::InProgress, Error::<T>::VoteNotInProgress);
+			let mut document = Self::get_document(vote.document_id.clone()).ok_or(Error::<T>::DocumentNotFound)?;
+			ensure!(document.status == DocumentStatus::VoteInProgress, Error::<T>::IncorrectDocumentStatus);
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(now > vote.end,Error::<T>::VoteStillInProgress);
+
+			let quorum = Self::get_round_quorum(VoteType::Verification,voting_id.clone())
+				.unwrap_or(VerificationQuorum::<T>::get()).into();
+			let total_votes = vote.yes_votes + vote.no_votes;
+
+			if total_votes < quorum {
+				vote.status = VoteStatus::Failed;
+				document.status = DocumentStatus::QuorumNotReached;
+			} else if vote.yes_votes > vote.no_votes {
+				vote.status = VoteStatus::Passed;
+				document.status = DocumentStatus::Verified;
+			} else {
+				vote.status = VoteStatus::Failed;
+				document.status = DocumentStatus::Rejected;
+			}
+
+			VerificationVotes::<T>::insert(voting_id.clone(),&vote);
+			Self::record_round_summary(VoteType::Verification,voting_id.clone(),&vote);
+			if total_votes >= quorum {
+				Self::record_winning_votes(VoteType::Verification,voting_id.clone(),vote.status == VoteStatus::Passed);
+			}
+			if vote.status == VoteStatus::Passed {
+				Activities::<T>::mutate(&document.creator,|activity| activity.documents_verified = activity.documents_verified.saturating_add(1));
+			}
+			Documents::<T>::insert(vote.document_id.clone(),&document);
+			Self::conclude_appeal(vote.document_id.clone(),VoteType::Verification,voting_id.clone(),vote.status,&document.creator);
+			Self::deposit_event(Event::DocumentStatusUpdated(vote.document_id,document.status as u8));
+			Self::deposit_event(Event::VerificationVotingEnded(voting_id));
+
+			Ok(Self::finalize_weight(total_votes,0))
+		}
+
+		/// Schedule `finalize_round` for the first block after a round ends that has room for it,
+		/// within `ROUND_SCHEDULING_ATTEMPTS` blocks. If none has, or the scheduler refuses it,
+		/// `RoundNotScheduled` is emitted and the round is left to be finalized by a member or to
+		/// expire.
+		fn schedule_round_finalization(vote_type: VoteType, voting_id: u64, end: T::BlockNumber) {
+			let max_rounds = T::MaxRoundsPerBlock::get();
+			let mut when = end.saturating_add(One::one());
+			for _ in 0..ROUND_SCHEDULING_ATTEMPTS {
+				if RoundsScheduledAt::<T>::get(when) < max_rounds {
+					let call: <T as Config>::Proposal = Call::<T>::finalize_round { vote_type, voting_id }.into();
+					let scheduled = T::Scheduler::schedule_named(
+						Self::round_task_id(vote_type,voting_id),
+						DispatchTime::At(when),
+						None,
+						SCHEDULE_PRIORITY,
+						frame_system::RawOrigin::Root.into(),
+						call,
+					);
+					if scheduled.is_ok() {
+						RoundsScheduledAt::<T>::mutate(when,|count| *count = count.saturating_add(1));
+						return;
+					}
+					break;
+				}
+				when = when.saturating_add(One::one());
+			}
+			Self::deposit_event(Event::RoundNotScheduled(vote_type,voting_id));
+		}
+
+		/// Drop the scheduled finalization of a round concluded some other way, such as by a
+		/// member after the scheduler failed to dispatch it.
+		fn cancel_round_finalization(vote_type: VoteType, voting_id: u64) {
+			let task = Self::round_task_id(vote_type,voting_id);
+			if let Ok(when) = T::Scheduler::next_dispatch_time(task.clone()) {
+				RoundsScheduledAt::<T>::mutate(when,|count| *count = count.saturating_sub(1));
+			}
+			let _ = T::Scheduler::cancel_named(task);
+		}
+
+		/// Block at which the scheduler will finalize a round, if it is still due to.
+		pub fn get_round_finalization(vote_type: VoteType, voting_id: u64) -> Option<T::BlockNumber> {
+			T::Scheduler::next_dispatch_time(Self::round_task_id(vote_type,voting_id)).ok()
+		}
+
+		fn round_task_id(vote_type: VoteType, voting_id: u64) -> Vec<u8> {
+			(ROUND_TASK_ID,vote_type,voting_id).encode()
+		}
+
+		/// Schedule `draw_review_panel` for the block a round's panel is due. If the scheduler
+		/// refuses it, the panel is left to be drawn by anyone.
+		fn schedule_panel_draw(voting_id: u64, when: T::BlockNumber) {
+			let call: <T as Config>::Proposal = Call::<T>::draw_review_panel { voting_id }.into();
+			let _ = T::Scheduler::schedule_named(
+				Self::panel_task_id(voting_id),
+				DispatchTime::At(when),
+				None,
+				SCHEDULE_PRIORITY,
+				frame_system::RawOrigin::Root.into(),
+				call,
+			);
+		}
+
+		fn panel_task_id(voting_id: u64) -> Vec<u8> {
+			(PANEL_TASK_ID,voting_id).encode()
+		}
+
+		/// The status a document had before the appeal that opened this round, if it is still open.
+		fn open_appeal_status(document_id: u64, vote_type: VoteType, voting_id: u64) -> Option<DocumentStatus> {
+			Self::get_appeals(document_id)
+				.last()
+				.filter(|appeal| appeal.vote_type == vote_type && appeal.voting_id == voting_id && appeal.outcome.is_none())
+				.map(|appeal| appeal.prior_status.clone())
+		}
+
+		/// Record the outcome of a round if it was opened by an appeal, and settle the deposit.
+		fn conclude_appeal(document_id: u64, vote_type: VoteType, voting_id: u64, outcome: VoteStatus, creator: &T::AccountId) {
+			let mut appeals = Self::get_appeals(document_id.clone());
+			if let Some(appeal) = appeals.last_mut() {
+				if appeal.vote_type != vote_type || appeal.voting_id != voting_id || appeal.outcome.is_some() {
+					return;
+				}
+				appeal.outcome = Some(outcome);
+				match outcome {
+					VoteStatus::Passed => {
+						T::Currency::unreserve(creator,appeal.deposit);
+					},
+					_ => {
+						let _ = T::Currency::slash_reserved(creator,appeal.deposit);
+					},
+				}
+				Appeals::<T>::insert(document_id.clone(),appeals);
+				Self::deposit_event(Event::AppealConcluded(document_id,outcome));
+			}
+		}
+
+		fn do_create_review_voting(document_id: u64, uid: u64) {
+			let now = <frame_system::Pallet<T>>::block_number();
+
+			let end = now + VerificationVotingWindow::<T>::get().into();
+
+			let vote = Vote::<T> {
+				document_id: document_id,
+				yes_votes: 0,
+				no_votes: 0,
+				start: now,
+				end: end,
+				status: VoteStatus::InProgress,
+			};
+
+			ReviewVotes::<T>::insert(uid.clone(),&vote);
+			ReviewVotesCount::<T>::put(uid.clone());
+			ActiveReviews::<T>::insert(document_id.clone(),uid.clone());
+			ExpiringRounds::<T>::append(Self::expiry_block(end),(VoteType::Review,uid.clone()));
+			DocumentRounds::<T>::append(document_id.clone(),(VoteType::Review,uid.clone()));
+			Self::deposit_event(Event::ReviewVotingStarted(document_id,uid));
+		}
+
+		/// Record the outcome of a review on the flags that opened it, and return or slash their
+		/// deposits.
+		fn conclude_review(document_id: u64, voting_id: u64, outcome: VoteStatus, slash: bool) {
+			let mut flags = Self::get_flags(document_id.clone());
+			for flag in flags.iter_mut().filter(|flag| flag.review == Some(voting_id)) {
+				flag.outcome = Some(outcome);
+				if slash {
+					let _ = T::Currency::slash_reserved(&flag.flagger,flag.deposit);
+				} else {
+					T::Currency::unreserve(&flag.flagger,flag.deposit);
+				}
+			}
+			Flags::<T>::insert(document_id.clone(),flags);
+			ActiveReviews::<T>::remove(document_id);
+		}
+
+		/// Fetch an exhibition that `who` can edit: it must exist and `who` must be a collector and
+		/// its curator.
+		fn get_curated_exhibition(who: T::AccountId, exhibition_id: u64) -> Result<Exhibition<T>, DispatchError> {
+			ensure!(Self::ensure_collector(who.clone()),Error::<T>::NotACollector);
+			let exhibition = Self::get_exhibition(exhibition_id).ok_or(Error::<T>::ExhibitionNotFound)?;
+			ensure!(exhibition.curator == who,Error::<T>::NotTheCurator);
+			Ok(exhibition)
+		}
+
+		/// All published exhibitions, with their ids.
+		pub fn get_published_exhibitions() -> Vec<(u64, Exhibition<T>)> {
+			Exhibitions::<T>::iter().filter(|(_, exhibition)| exhibition.published).collect()
+		}
+
+		/// Whether `who` is the creator of the document or an account related to them.
+		pub fn has_conflict(who: &T::AccountId, document: &Document<T>) -> bool {
+			*who == document.creator || T::Relations::are_related(&document.creator, who)
+		}
+
+		/// Qualifiers who can be drawn to review a document: those without a conflict on it, not
+		/// suspended, and not already on `MaxPanelAssignments` open panels.
+		fn panel_candidates(document_id: u64, document: &Document<T>) -> Vec<T::AccountId> {
+			let max_load = T::MaxPanelAssignments::get();
+			Qualifiers::<T>::get()
+				.into_iter()
+				.filter(|member| !Self::has_conflict(member, document))
+				.filter(|member| !Recusals::<T>::contains_key(document_id, member))
+				.filter(|member| Self::get_workload(member) < max_load)
+				.filter(|member| pallet_nft::Pallet::<T>::holds_unfrozen_token(Roles::QualifierRole as u32,member))
+				.collect()
+		}
+
+		/// Draw `size` of the panel candidates at random to review a qualification round.
+		fn do_draw_review_panel(voting_id: u64, document_id: u64, document: &Document<T>, size: u32) -> Result<Vec<T::AccountId>, DispatchError> {
+			let mut candidates = Self::panel_candidates(document_id, document);
+			let size = size as usize;
+			ensure!(candidates.len() >= size,Error::<T>::NotEnoughReviewers);
+
+			// Partial Fisher-Yates shuffle, with one hash of the seed per draw
+			let (seed, _) = T::Randomness::random(&(b"bhdao/panel", voting_id).encode());
+			for i in 0..size {
+				let hash = sp_io::hashing::blake2_256(&(seed, i as u32).encode());
+				let random = u32::from_le_bytes([hash[0], hash[1], hash[2], hash[3]]) as usize;
+				let j = i + random % (candidates.len() - i);
+				candidates.swap(i, j);
+			}
+			candidates.truncate(size);
+
+			Ok(candidates)
+		}
+
+		/// Mean of the scorecards cast in a qualification round, rounded down, or `None` if nobody
+		/// voted. A mean reaches a threshold exactly when the rounded down mean does.
+		pub fn mean_scores(voting_id: u64) -> Option<Scorecard> {
+			let (totals, ballots) = ScoreTotals::<T>::get(voting_id);
+			if ballots == 0 {
+				return None;
+			}
+
+			let mean = |total: u32| (total / ballots) as u8;
+			Some(Scorecard {
+				authenticity: mean(totals[0]),
+				relevance: mean(totals[1]),
+				completeness: mean(totals[2]),
+				originality: mean(totals[3]),
+			})
+		}
+
+		/// Free the seats of a concluded round's panel, so its members can be drawn again, or drop
+		/// the draw if it has not happened.
+		fn release_review_panel(voting_id: u64) {
+			if let Some(panel) = Self::get_review_panel(voting_id) {
+				for member in panel.iter() {
+					Workloads::<T>::mutate(member, |load| *load = load.saturating_sub(1));
+				}
+			}
+			if PendingPanels::<T>::take(voting_id).is_some() {
+				let _ = T::Scheduler::cancel_named(Self::panel_task_id(voting_id));
+			}
+		}
+
+		/// First block at which a round ending at `end` can be expired.
+		fn expiry_block(end: T::BlockNumber) -> T::BlockNumber {
+			end.saturating_add(T::ExpiryGracePeriod::get()).saturating_add(One::one())
+		}
+
+		pub fn do_expire_vote(vote_type: VoteType, voting_id: u64) -> DispatchResult {
+			let vote = Self::get_vote(vote_type, voting_id).ok_or(Error::<T>::VoteNotFound)?;
+			ensure!(vote.status == VoteStatus::InProgress, Error::<T>::VoteNotInProgress);
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(now >= Self::expiry_block(vote.end),Error::<T>::VoteNotExpired);
+
+			Self::expire_round(vote_type, voting_id, vote)
+		}
+
+		/// Mark a round `Expired` and put its document back to the state it had before the round.
+		fn expire_round(vote_type: VoteType, voting_id: u64, mut vote: Vote<T>) -> DispatchResult {
+			vote.status = VoteStatus::Expired;
+			match vote_type {
+				VoteType::Qualification | VoteType::Verification => {
+					let mut document = Self::get_document(vote.document_id.clone()).ok_or(Error::<T>::DocumentNotFound)?;
+					if vote_type == VoteType::Qualification {
+						document.status = DocumentStatus::Submitted;
+						QualificationVotes::<T>::insert(voting_id.clone(),&vote);
+						Self::release_review_panel(voting_id.clone());
+					} else {
+						document.status = DocumentStatus::SuccessfulReview;
+						VerificationVotes::<T>::insert(voting_id.clone(),&vote);
+					}
+					// An expired appeal leaves the rejection it appealed in place
+					if let Some(prior_status) = Self::open_appeal_status(vote.document_id.clone(),vote_type,voting_id.clone()) {
+						document.status = prior_status;
+					}
+					Documents::<T>::insert(vote.document_id.clone(),&document);
+					Self::cancel_round_finalization(vote_type,voting_id.clone());
+					Self::conclude_appeal(vote.document_id.clone(),vote_type,voting_id.clone(),vote.status,&document.creator);
+					Self::deposit_event(Event::DocumentStatusUpdated(vote.document_id,document.status as u8));
+				},
+				VoteType::Proposal => {
+					ProposalVotes::<T>::insert(voting_id.clone(),&vote);
+					Proposals::<T>::remove(voting_id.clone());
+				},
+				// The document stays verified
+				VoteType::Review => {
+					ReviewVotes::<T>::insert(voting_id.clone(),&vote);
+					Self::conclude_review(vote.document_id.clone(),voting_id.clone(),vote.status,false);
+				},
+			}
+			Self::record_round_summary(vote_type,voting_id.clone(),&vote);
+			Self::deposit_event(Event::VoteExpired(vote_type,voting_id));
+
+			Ok(())
+		}
+
+		/// Every ballot cast in a round that has not been pruned yet.
+		pub fn get_round_ballots(vote_type: VoteType, voting_id: u64) -> Vec<(T::AccountId, bool)> {
+			MemberVotes::<T>::iter_prefix((vote_type, voting_id)).collect()
+		}
+
+		fn record_round_summary(vote_type: VoteType, voting_id: u64, vote: &Vote<T>) {
+			let now = <frame_system::Pallet<T>>::block_number();
+			let summary = RoundSummary {
+				yes_votes: vote.yes_votes,
+				no_votes: vote.no_votes,
+				status: vote.status,
+				finalized_at: now,
+			};
+			RoundSummaries::<T>::insert(vote_type,voting_id,summary);
+			PruneQueue::<T>::append(now.saturating_add(T::BallotRetentionPeriod::get()),(vote_type,voting_id));
+		}
+
+		/// Count a successful extrinsic from `who`.
+		fn record_transaction(who: &T::AccountId) {
+			TotalTransactions::<T>::mutate(|total| *total = total.saturating_add(1));
+			TransactionsPerAddress::<T>::mutate(who,|count| *count = Some(count.unwrap_or_default().saturating_add(1)));
+		}
+
+		/// Credit every ballot on the winning side of a round decided on the votes.
+		fn record_winning_votes(vote_type: VoteType, voting_id: u64, passed: bool) {
+			for (who, vote_cast) in MemberVotes::<T>::iter_prefix((vote_type,voting_id)) {
+				if vote_cast == passed {
+					Activities::<T>::mutate(&who,|activity| activity.winning_votes = activity.winning_votes.saturating_add(1));
+				}
+			}
+		}
+
+		/// The roles, transaction count and activity of a member, or `None` for non-members.
+		pub fn get_member_profile(who: T::AccountId) -> Option<MemberProfile> {
+			let roles: Vec<Roles> = [Roles::QualifierRole, Roles::CollectorRole, Roles::ContributorRole]
+				.into_iter()
+				.filter(|role| Self::get_roster(*role).binary_search(&who).is_ok())
+				.collect();
+			if roles.is_empty() {
+				return None;
+			}
+
+			Some(MemberProfile {
+				roles,
+				transactions: Self::get_transactions_per_address(&who).unwrap_or_default(),
+				activity: Self::get_activity(&who),
+			})
+		}
+
+		/// Remove the ballots of rounds finalized more than `BallotRetentionPeriod` blocks ago,
+		/// as far as `remaining_weight` allows. Queue entries are read from `PruneCursor` up to
+		/// `now`, so a block whose rounds are only partly pruned is picked up again next time.
+		fn prune_ballots(now: T::BlockNumber, remaining_weight: Weight) -> Weight {
+			let db_weight = T::DbWeight::get();
+			let mut used = db_weight.reads_writes(1,1);
+			if remaining_weight <= used {
+				return 0;
+			}
+
+			let per_block = db_weight.reads_writes(1,1);
+			let per_ballot = db_weight.writes(1).max(1);
+			let mut cursor = Self::get_prune_cursor();
+
+			while cursor <= now && used.saturating_add(per_block) <= remaining_weight {
+				used = used.saturating_add(per_block);
+				let mut rounds = PruneQueue::<T>::get(cursor);
+				let mut pruned = 0;
+				let mut done = true;
+
+				for (vote_type, voting_id) in rounds.iter() {
+					let limit = remaining_weight.saturating_sub(used) / per_ballot;
+					if limit == 0 {
+						done = false;
+						break;
+					}
+					match MemberVotes::<T>::remove_prefix((vote_type, voting_id), Some(limit.min(u32::MAX as u64) as u32)) {
+						sp_io::KillStorageResult::AllRemoved(removed) => {
+							used = used.saturating_add(per_ballot.saturating_mul(removed as u64));
+							pruned += 1;
+						},
+						sp_io::KillStorageResult::SomeRemaining(removed) => {
+							used = used.saturating_add(per_ballot.saturating_mul(removed as u64));
+							done = false;
+							break;
+						},
+					}
+				}
+
+				if !done {
+					rounds.drain(..pruned);
+					PruneQueue::<T>::insert(cursor,rounds);
+					break;
+				}
+				PruneQueue::<T>::remove(cursor);
+				cursor = cursor.saturating_add(One::one());
+			}
+			PruneCursor::<T>::put(cursor);
+
+			used
+		}
+
+		pub fn ensure_member(who: T::AccountId) -> bool {
+			Self::ensure_qualifier(who.clone()) || Self::ensure_collector(who.clone()) || Self::ensure_contributor(who)
+		}
+
 		pub fn update_document_status(document_uid: u64, status: u8) -> DispatchResult {
 			let mut document = Self::get_document(document_uid).ok_or(Error::<T>::DocumentNotFound)?;
 
@@ -734,6 +2756,15 @@ pub mod pallet {
 				5 => {
 					document.status = DocumentStatus::Rejected;
 				},
+				6 => {
+					document.status = DocumentStatus::QuorumNotReached;
+				},
+				7 => {
+					document.status = DocumentStatus::Revoked;
+				},
+				8 => {
+					document.status = DocumentStatus::Quarantined;
+				},
 				_ => ()
 			}
 
@@ -742,6 +2773,163 @@ pub mod pallet {
 
 			Ok(())
 		}
+
+		pub fn get_vote(vote_type: VoteType, voting_id: u64) -> Option<Vote<T>> {
+			match vote_type {
+				VoteType::Qualification => Self::get_qualification_vote(voting_id),
+				VoteType::Verification => Self::get_verification_vote(voting_id),
+				VoteType::Proposal => Self::get_proposal_vote(voting_id),
+				VoteType::Review => Self::get_review_vote(voting_id),
+			}
+		}
+
+		/// Every voting round held on a document, oldest first, with the rationales qualifiers
+		/// attached to their ballots.
+		pub fn get_document_history(document_id: u64) -> Vec<VotingRound<T>> {
+			Self::get_document_rounds(document_id)
+				.into_iter()
+				.filter_map(|(vote_type, voting_id)| {
+					let vote = Self::get_vote(vote_type, voting_id)?;
+					let rationales = match vote_type {
+						VoteType::Qualification => QualificationRationales::<T>::iter_prefix(voting_id).collect(),
+						_ => Vec::new(),
+					};
+					Some(VotingRound::<T> { vote_type, voting_id, vote, rationales })
+				})
+				.collect()
+		}
+
+		/// Whether `call` is a voting or finalization call that members can make without fees.
+		pub fn is_fee_free_call(call: &Call<T>) -> bool {
+			matches!(call,
+				Call::cast_qualification_vote { .. } |
+				Call::cast_verification_vote { .. } |
+				Call::cast_review_vote { .. } |
+				Call::cast_proposal_vote { .. } |
+				Call::finalize_qualification_voting { .. } |
+				Call::finalize_verification_voting { .. } |
+				Call::finalize_review_voting { .. } |
+				Call::finalize_proposal_voting { .. }
+			)
+		}
+
+		fn current_fee_era() -> T::BlockNumber {
+			let now = <frame_system::Pallet<T>>::block_number();
+			now / T::FreeCallEraLength::get().max(One::one())
+		}
+
+		/// The current era and the fee-free calls `who` has used in it, if `who` is a member with
+		/// calls left.
+		pub fn next_free_call(who: &T::AccountId) -> Option<(T::BlockNumber, u32)> {
+			if !Self::ensure_member(who.clone()) {
+				return None;
+			}
+			let era = Self::current_fee_era();
+			let (last_era, used) = Self::get_free_calls(who);
+			let used = if last_era == era { used } else { 0 };
+			if used < T::FreeCallsPerEra::get() {
+				Some((era, used))
+			} else {
+				None
+			}
+		}
+
+		/// Whether `who` is a member with fee-free calls left in the current era.
+		pub fn has_free_call(who: &T::AccountId) -> bool {
+			Self::next_free_call(who).is_some()
+		}
+
+		/// The number of blocks left in the fee era `era`.
+		pub(crate) fn fee_era_blocks_left(era: T::BlockNumber) -> T::BlockNumber {
+			let now = <frame_system::Pallet<T>>::block_number();
+			let length = T::FreeCallEraLength::get().max(One::one());
+			era.saturating_add(One::one()).saturating_mul(length).saturating_sub(now)
+		}
+
+		/// Count a fee-free call against the quota of `who`.
+		pub fn use_free_call(who: &T::AccountId) {
+			let era = Self::current_fee_era();
+			FreeCalls::<T>::mutate(who, |(last_era, used)| {
+				if *last_era != era {
+					*last_era = era;
+					*used = 0;
+				}
+				*used = used.saturating_add(1);
+			});
+		}
+
+		/// Check that membership, tokens and qualification rounds agree with each other. Polkadot
+		/// v0.9.18 has no `try_state` hook, so this only runs from the migration's `post_upgrade`
+		/// under `try-runtime` and from the tests, never on every block.
+		#[cfg(any(feature = "try-runtime", test))]
+		pub fn do_try_state() -> Result<(), &'static str> {
+			let rosters = [
+				(Qualifiers::<T>::get(), QualifiersCount::<T>::get(), Roles::QualifierRole),
+				(Collectors::<T>::get(), CollectorsCount::<T>::get(), Roles::CollectorRole),
+				(Contributors::<T>::get(), ContributorsCount::<T>::get(), Roles::ContributorRole),
+			];
+			// Members whose badge was burned stay listed but cannot act, so only the other way
+			// round is checked
+			for (members, count, role) in rosters.iter() {
+				ensure!(members.len() as u32 <= *count, "more members than uids handed out");
+				ensure!(
+					pallet_nft::Pallet::<T>::holders(*role as u32).iter().all(|(_, who)| members.binary_search(who).is_ok()),
+					"role token holder is not a member"
+				);
+			}
+
+			let mut open_rounds = sp_std::collections::btree_map::BTreeMap::<u64, u32>::new();
+			for (_, vote) in QualificationVotes::<T>::iter() {
+				if vote.status == VoteStatus::InProgress {
+					*open_rounds.entry(vote.document_id).or_default() += 1;
+				}
+			}
+			for (document_id, document) in Documents::<T>::iter() {
+				let rounds = open_rounds.get(&document_id).copied().unwrap_or_default();
+				if document.status == DocumentStatus::UnderReview {
+					ensure!(rounds == 1, "document under review does not have exactly one open qualification round");
+				} else {
+					ensure!(rounds == 0, "open qualification round on a document not under review");
+				}
+			}
+
+			Ok(())
+		}
 		
 	}
 }
+
+/// Ensures that the origin is a proposal passed by the DAO.
+pub struct EnsureDao;
+impl<O: Into<Result<RawOrigin, O>> + From<RawOrigin>> frame_support::traits::EnsureOrigin<O> for EnsureDao {
+	type Success = ();
+	fn try_origin(o: O) -> Result<Self::Success, O> {
+		o.into().and_then(|o| match o {
+			RawOrigin::Dao => Ok(()),
+		})
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn successful_origin() -> O {
+		O::from(RawOrigin::Dao)
+	}
+}
+
+/// Ensures that the origin is signed by the DAO administrator, the account in `Key`.
+pub struct EnsureKey<T>(sp_std::marker::PhantomData<T>);
+impl<T: Config, O: Into<Result<frame_system::RawOrigin<T::AccountId>, O>> + From<frame_system::RawOrigin<T::AccountId>>>
+	frame_support::traits::EnsureOrigin<O> for EnsureKey<T>
+{
+	type Success = T::AccountId;
+	fn try_origin(o: O) -> Result<Self::Success, O> {
+		o.into().and_then(|o| match o {
+			frame_system::RawOrigin::Signed(who) if Key::<T>::get().as_ref() == Some(&who) => Ok(who),
+			r => Err(O::from(r)),
+		})
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn successful_origin() -> O {
+		O::from(frame_system::RawOrigin::Signed(Key::<T>::get().expect("the DAO key is set")))
+	}
+}
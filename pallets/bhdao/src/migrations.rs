@@ -0,0 +1,237 @@
+//! Storage migrations for the bhdao pallet.
+//!
+//! Each `vN` module moves storage from version `N - 1` to `N`, using typed definitions of the old
+//! layout where it changed. `Migration` runs the pending ones and is registered in the runtime's
+//! `Executive`.
+
+use crate::pallet::Config;
+use frame_support::{pallet_prelude::*, traits::OnRuntimeUpgrade};
+#[cfg(feature = "try-runtime")]
+use crate::pallet::{Documents, Pallet, STORAGE_VERSION};
+#[cfg(feature = "try-runtime")]
+use frame_support::traits::{GetStorageVersion, OnRuntimeUpgradeHelpersExt};
+use sp_std::marker::PhantomData;
+
+/// Brings bhdao storage up to `STORAGE_VERSION` from any earlier version.
+pub struct Migration<T>(PhantomData<T>);
+
+impl<T: Config> OnRuntimeUpgrade for Migration<T> {
+	fn on_runtime_upgrade() -> Weight {
+		v1::migrate::<T>() + v2::migrate::<T>() + v3::migrate::<T>() + v4::migrate::<T>() + v5::migrate::<T>()
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<(), &'static str> {
+		Self::set_temp_storage(Documents::<T>::iter_keys().count() as u64, "documents");
+		Ok(())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade() -> Result<(), &'static str> {
+		ensure!(Pallet::<T>::on_chain_storage_version() == STORAGE_VERSION, "bhdao storage version was not updated");
+		// Entries that fail to decode are skipped by `iter`, so a layout mismatch shows up here
+		let documents: u64 = Self::get_temp_storage("documents").ok_or("document count was not recorded")?;
+		ensure!(Documents::<T>::iter().count() as u64 == documents, "some documents do not decode");
+		Pallet::<T>::do_try_state()
+	}
+}
+
+pub mod v1 {
+	use crate::pallet::{Config, MemberVotes, Pallet, PruneQueue, VoteStatus, VoteType};
+	use frame_support::{
+		pallet_prelude::*,
+		storage::migration::storage_key_iter,
+		traits::{GetStorageVersion, PalletInfoAccess, StorageVersion},
+		Blake2_128Concat,
+	};
+	use sp_runtime::traits::Saturating;
+	use sp_std::vec::Vec;
+
+	/// Move ballots from the old `MemberVote` map, keyed by `(voter, vote type, round)`, into
+	/// `MemberVotes`, keyed by round then voter.
+	///
+	/// Rounds closed before the upgrade have no `RoundSummaries` record. Their ballots are queued
+	/// for pruning as if the rounds had been finalized in the upgrade block.
+	pub fn migrate<T: Config>() -> Weight {
+		let on_chain = Pallet::<T>::on_chain_storage_version();
+		if on_chain >= 1 {
+			return T::DbWeight::get().reads(1);
+		}
+
+		let mut moved: u64 = 0;
+		let mut rounds: Vec<(VoteType, u64)> = Vec::new();
+		for ((who, vote_type, voting_id), vote_cast) in storage_key_iter::<
+			(T::AccountId, VoteType, u64),
+			bool,
+			Blake2_128Concat,
+		>(Pallet::<T>::name().as_bytes(), b"MemberVote")
+		.drain()
+		{
+			MemberVotes::<T>::insert((vote_type, voting_id, who), vote_cast);
+			if !rounds.contains(&(vote_type, voting_id)) {
+				rounds.push((vote_type, voting_id));
+			}
+			moved += 1;
+		}
+
+		// Rounds still open are queued by their own finalization
+		let due = <frame_system::Pallet<T>>::block_number().saturating_add(T::BallotRetentionPeriod::get());
+		let closed: Vec<(VoteType, u64)> = rounds
+			.iter()
+			.filter(|(vote_type, voting_id)| {
+				Pallet::<T>::get_vote(*vote_type, *voting_id).map_or(true, |vote| vote.status != VoteStatus::InProgress)
+			})
+			.copied()
+			.collect();
+		let queued = rounds.len() as u64;
+		PruneQueue::<T>::mutate(due, |queue| queue.extend(closed));
+
+		StorageVersion::new(1).put::<Pallet<T>>();
+
+		T::DbWeight::get().reads_writes(moved + queued + 2, moved * 2 + 2)
+	}
+}
+
+pub mod v2 {
+	use crate::pallet::{Config, Document, DocumentStatus, Documents, Pallet};
+	use frame_support::{
+		pallet_prelude::*,
+		traits::{GetStorageVersion, StorageVersion},
+	};
+	use sp_std::vec::Vec;
+
+	/// `Document` before it carried qualification scores.
+	#[derive(Decode)]
+	pub struct OldDocument<AccountId> {
+		pub creator: AccountId,
+		pub title: Vec<u8>,
+		pub description: Vec<u8>,
+		pub format: Vec<u8>,
+		pub hash: Vec<u8>,
+		pub status: DocumentStatus,
+	}
+
+	/// Add empty `scores` to every document. Documents qualified before the upgrade were voted
+	/// on with yes/no ballots, so they have no scores to show.
+	pub fn migrate<T: Config>() -> Weight {
+		let on_chain = Pallet::<T>::on_chain_storage_version();
+		if on_chain != 1 {
+			return T::DbWeight::get().reads(1);
+		}
+
+		let mut translated: u64 = 0;
+		Documents::<T>::translate::<OldDocument<T::AccountId>, _>(|_, old| {
+			translated += 1;
+			Some(Document::<T> {
+				creator: old.creator,
+				title: old.title,
+				description: old.description,
+				format: old.format,
+				hash: old.hash,
+				status: old.status,
+				scores: None,
+			})
+		});
+
+		StorageVersion::new(2).put::<Pallet<T>>();
+
+		T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+	}
+}
+
+pub mod v3 {
+	use crate::pallet::{Config, Pallet, RecusalCounts, Recusals};
+	use frame_support::{
+		pallet_prelude::*,
+		traits::{GetStorageVersion, StorageVersion},
+	};
+
+	/// Count the recusals already declared on each document into `RecusalCounts`.
+	pub fn migrate<T: Config>() -> Weight {
+		let on_chain = Pallet::<T>::on_chain_storage_version();
+		if on_chain != 2 {
+			return T::DbWeight::get().reads(1);
+		}
+
+		let mut recusals: u64 = 0;
+		for (document_id, _) in Recusals::<T>::iter_keys() {
+			RecusalCounts::<T>::mutate(document_id, |count| *count = count.saturating_add(1));
+			recusals += 1;
+		}
+
+		StorageVersion::new(3).put::<Pallet<T>>();
+
+		T::DbWeight::get().reads_writes(recusals * 2 + 1, recusals + 1)
+	}
+}
+
+pub mod v4 {
+	use crate::pallet::{Config, Pallet, ScoreTotals, Scorecards};
+	use frame_support::{
+		pallet_prelude::*,
+		traits::{GetStorageVersion, StorageVersion},
+	};
+
+	/// Sum the scorecards already cast in each qualification round into `ScoreTotals`.
+	pub fn migrate<T: Config>() -> Weight {
+		let on_chain = Pallet::<T>::on_chain_storage_version();
+		if on_chain != 3 {
+			return T::DbWeight::get().reads(1);
+		}
+
+		let mut scorecards: u64 = 0;
+		for (voting_id, _, scorecard) in Scorecards::<T>::iter() {
+			ScoreTotals::<T>::mutate(voting_id, |(totals, ballots)| {
+				for (total, score) in totals.iter_mut().zip(scorecard.scores().iter()) {
+					*total = total.saturating_add(*score as u32);
+				}
+				*ballots = ballots.saturating_add(1);
+			});
+			scorecards += 1;
+		}
+
+		StorageVersion::new(4).put::<Pallet<T>>();
+
+		T::DbWeight::get().reads_writes(scorecards * 2 + 1, scorecards + 1)
+	}
+}
+
+pub mod v5 {
+	use crate::pallet::{Config, Pallet, PruneCursor, PruneQueue, VoteType};
+	use frame_support::{
+		pallet_prelude::*,
+		storage::migration::take_storage_value,
+		traits::{GetStorageVersion, PalletInfoAccess, StorageVersion},
+	};
+	use sp_runtime::traits::Saturating;
+	use sp_std::vec::Vec;
+
+	/// Move the single `PruneQueue` list of `(finalized at, vote type, round)` into `PruneQueue`
+	/// keyed by the block each round's retention period ends, no earlier than the upgrade block,
+	/// and start `PruneCursor` at the upgrade block.
+	pub fn migrate<T: Config>() -> Weight {
+		let on_chain = Pallet::<T>::on_chain_storage_version();
+		if on_chain != 4 {
+			return T::DbWeight::get().reads(1);
+		}
+
+		let now = <frame_system::Pallet<T>>::block_number();
+		let retention = T::BallotRetentionPeriod::get();
+		let queue = take_storage_value::<Vec<(T::BlockNumber, VoteType, u64)>>(
+			Pallet::<T>::name().as_bytes(),
+			b"PruneQueue",
+			&[],
+		)
+		.unwrap_or_default();
+		let rounds = queue.len() as u64;
+		for (finalized_at, vote_type, voting_id) in queue {
+			let due = finalized_at.saturating_add(retention).max(now);
+			PruneQueue::<T>::append(due, (vote_type, voting_id));
+		}
+		PruneCursor::<T>::put(now);
+
+		StorageVersion::new(5).put::<Pallet<T>>();
+
+		T::DbWeight::get().reads_writes(rounds + 2, rounds + 3)
+	}
+}
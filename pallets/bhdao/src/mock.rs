@@ -1,6 +1,7 @@
 use crate as pallet_bhdao;
-use frame_support::traits::{ConstU16, ConstU32, ConstU64, ConstU128,OnFinalize, OnInitialize};
+use frame_support::traits::{ConstU8, ConstU16, ConstU32, ConstU64, ConstU128, EnsureOneOf, EqualPrivilegeOnly, OnFinalize, OnInitialize};
 use frame_system as system;
+use frame_system::EnsureRoot;
 use sp_core::H256;
 use sp_runtime::{
 	testing::Header,
@@ -20,9 +21,11 @@ frame_support::construct_runtime!(
 		UncheckedExtrinsic = UncheckedExtrinsic,
 	{
 		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
-		Bhdao: pallet_bhdao::{Pallet, Call, Storage, Event<T>},
+		Bhdao: pallet_bhdao::{Pallet, Call, Storage, Event<T>, Origin},
 		Balances: pallet_balances,
 		Nft: pallet_nft,
+		Scheduler: pallet_scheduler,
+		RandomnessCollectiveFlip: pallet_randomness_collective_flip,
 	}
 );
 
@@ -56,10 +59,46 @@ impl system::Config for Test {
 impl pallet_bhdao::Config for Test {
 	type Event = Event;
 	type Currency = Balances;
+	type MaxRationaleNoteLength = ConstU32<64>;
+	type MaxScore = ConstU8<5>;
+	type Origin = Origin;
+	type Proposal = Call;
+	type PalletsOrigin = OriginCaller;
+	type Scheduler = Scheduler;
+	type GovernanceOrigin = EnsureOneOf<EnsureRoot<u64>, pallet_bhdao::EnsureDao>;
+	type AdminOrigin = pallet_bhdao::EnsureKey<Test>;
+	type AppealDeposit = ConstU128<1_000>;
+	type MaxAppeals = ConstU32<2>;
+	type AppealQuorumIncrease = ConstU32<1>;
+	type FlagDeposit = ConstU128<500>;
+	type FlagThreshold = ConstU32<2>;
+	type MaxQualifiers = ConstU32<200>;
+	type MaxCollectors = ConstU32<100>;
+	type MaxContributors = ConstU32<1_000>;
+	type MaxMembers = ConstU32<1_000>;
+	type MaxVotesPerRound = ConstU32<3>;
+	type MaxBatchSize = ConstU32<3>;
+	type MaxExhibits = ConstU32<3>;
+	type BallotRetentionPeriod = ConstU64<10>;
+	type ExpiryGracePeriod = ConstU64<20>;
+	type MaxRoundsPerBlock = ConstU32<2>;
+	type Randomness = RandomnessCollectiveFlip;
+	type PanelDrawDelay = ConstU64<5>;
+	type MaxPanelAssignments = ConstU32<1>;
+	type FreeCallsPerEra = ConstU32<2>;
+	type FreeCallEraLength = ConstU64<10>;
+	type Relations = ();
 }
 
 impl pallet_nft::Config for Test {
 	type Event = Event;
+	type Currency = Balances;
+	type CollectionDeposit = ConstU128<100>;
+	type MetadataLimit = ConstU32<32>;
+	type KeyLimit = ConstU32<8>;
+	type ValueLimit = ConstU32<16>;
+	type MaxAttributes = ConstU32<3>;
+	type ReservedCollections = ConstU32<5>;
 }
 
 impl pallet_balances::Config for Test {
@@ -76,15 +115,39 @@ impl pallet_balances::Config for Test {
 	type WeightInfo = ();
 }
 
+impl pallet_scheduler::Config for Test {
+	type Event = Event;
+	type Origin = Origin;
+	type PalletsOrigin = OriginCaller;
+	type Call = Call;
+	type MaximumWeight = ConstU64<1_000_000_000>;
+	type ScheduleOrigin = EnsureRoot<u64>;
+	type MaxScheduledPerBlock = ConstU32<50>;
+	type WeightInfo = ();
+	type OriginPrivilegeCmp = EqualPrivilegeOnly;
+	type PreimageProvider = ();
+	type NoPreimagePostponement = ();
+}
+
+impl pallet_randomness_collective_flip::Config for Test {}
+
 pub fn run_to_block(n: u64) {
 	while System::block_number() < n {
 		Bhdao::on_finalize(System::block_number());
 		System::set_block_number(System::block_number() + 1);
 		Bhdao::on_initialize(System::block_number());
+		Scheduler::on_initialize(System::block_number());
 	}
 }
 
-// Build genesis storage according to the mock runtime.
+// Build genesis storage according to the mock runtime, with the role collections created.
 pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut ext = bare_test_ext();
+	ext.execute_with(|| Bhdao::init_collections(Origin::root()).unwrap());
+	ext
+}
+
+// Build genesis storage according to the mock runtime, without any collections.
+pub fn bare_test_ext() -> sp_io::TestExternalities {
 	system::GenesisConfig::default().build_storage::<Test>().unwrap().into()
 }
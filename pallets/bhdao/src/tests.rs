@@ -1,5 +1,18 @@
-use crate::{mock::*, Error};
-use frame_support::{assert_noop, assert_ok};
+use crate::{fees::{WaiveVotingFees, FREE_CALL_PRIORITY}, migrations::Migration, mock::*, Activity, DocumentStatus, Error, FlagReason, Rationale, ReasonCode, Roles, Scorecard, VoteStatus, VoteType};
+use codec::Encode;
+use frame_support::{
+	assert_noop, assert_ok,
+	storage::migration::{get_storage_value, put_storage_value},
+	traits::{Currency, GetStorageVersion, Hooks, OnRuntimeUpgrade, ReservableCurrency, StorageVersion},
+	weights::GetDispatchInfo,
+	Blake2_128Concat, StorageHasher,
+};
+use sp_runtime::{traits::SignedExtension, DispatchError};
+
+// A scorecard giving the same score on every criterion. The default thresholds in the mock are 3.
+fn scorecard(score: u8) -> Scorecard {
+	Scorecard { authenticity: score, relevance: score, completeness: score, originality: score }
+}
 
 #[test]
 fn it_adds_qualifier_should_work() {
@@ -126,11 +139,12 @@ fn it_creates_and_finalizes_qualification_voting_should_work() {
 #[test]
 fn it_casts_votes_and_verifies_voting_should_work() {
 	new_test_ext().execute_with(|| {
-		assert_ok!(Bhdao::init_collections(Origin::root()));
 		//Create Three contributors
 		assert_ok!(Bhdao::add_contributor(Origin::root(),1));
 		assert_ok!(Bhdao::add_contributor(Origin::root(),2));
 		assert_ok!(Bhdao::add_contributor(Origin::root(),3));
+		// and the creator of the document, who cannot vote on it
+		assert_ok!(Bhdao::add_contributor(Origin::root(),7));
 		// Create three qualifiers
 		assert_ok!(Bhdao::add_qualifier(Origin::root(),4));
 		assert_ok!(Bhdao::add_qualifier(Origin::root(),5));
@@ -138,7 +152,7 @@ fn it_casts_votes_and_verifies_voting_should_work() {
 
 		// create a new document
 
-		assert_ok!(Bhdao::create_document(Origin::signed(2),b"Doc1".to_vec(),b"Test1".to_vec(),b"pdf".to_vec(),b"https://ipfs.hash".to_vec()));
+		assert_ok!(Bhdao::create_document(Origin::signed(7),b"Doc1".to_vec(),b"Test1".to_vec(),b"pdf".to_vec(),b"https://ipfs.hash".to_vec()));
 		assert_eq!(Bhdao::get_total_items(),1);
 
 		run_to_block(10);
@@ -154,15 +168,15 @@ fn it_casts_votes_and_verifies_voting_should_work() {
 		run_to_block(20);
 
 		//cast votes two Yays One nay
-		assert_ok!(Bhdao::cast_qualification_vote(Origin::signed(4),1,true));
-		assert_ok!(Bhdao::cast_qualification_vote(Origin::signed(5),1,true));
-		assert_ok!(Bhdao::cast_qualification_vote(Origin::signed(6),1,false));
+		assert_ok!(Bhdao::cast_qualification_vote(Origin::signed(4),1,scorecard(4),None));
+		assert_ok!(Bhdao::cast_qualification_vote(Origin::signed(5),1,scorecard(4),None));
+		assert_ok!(Bhdao::cast_qualification_vote(Origin::signed(6),1,scorecard(1),None));
 
 		// Skip 100 blocks
 		run_to_block(120);
 
-		// Finalize qualification voting
-		assert_ok!(Bhdao::finalize_qualification_voting(Origin::signed(4),1));
+		// The round was finalized by the scheduler
+		assert_eq!(Bhdao::get_document(1).unwrap().status,DocumentStatus::SuccessfulReview);
 
 		// Change Verification Voting Window to 100 blocks
 		assert_ok!(Bhdao::set_verification_voting_window(Origin::root(),100u32));
@@ -182,7 +196,1086 @@ fn it_casts_votes_and_verifies_voting_should_work() {
 		// Skip 100 blocks
 		run_to_block(255);
 
-		// Finalize verification voting
-		assert_ok!(Bhdao::finalize_verification_voting(Origin::signed(2),1));
+		// The round was finalized by the scheduler
+		assert_eq!(Bhdao::get_document(1).unwrap().status,DocumentStatus::Verified);
 	});	
-}
\ No newline at end of file
+}
+
+#[test]
+fn it_records_rationale_in_document_history_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Bhdao::add_contributor(Origin::root(),2));
+		assert_ok!(Bhdao::add_qualifier(Origin::root(),4));
+		assert_ok!(Bhdao::add_qualifier(Origin::root(),5));
+		assert_ok!(Bhdao::create_document(Origin::signed(2),b"Doc1".to_vec(),b"Test1".to_vec(),b"pdf".to_vec(),b"https://ipfs.hash".to_vec()));
+		assert_ok!(Bhdao::create_qualification_voting(Origin::signed(4),1));
+
+		run_to_block(5);
+
+		let rationale = Rationale { reason: ReasonCode::Duplicate, note: Some(b"QmNote".to_vec()) };
+		assert_ok!(Bhdao::cast_qualification_vote(Origin::signed(4),1,scorecard(1),Some(rationale.clone())));
+		assert_ok!(Bhdao::cast_qualification_vote(Origin::signed(5),1,scorecard(4),None));
+
+		let history = Bhdao::get_document_history(1);
+		assert_eq!(history.len(),1);
+		assert_eq!(history[0].vote_type,VoteType::Qualification);
+		assert_eq!(history[0].voting_id,1);
+		assert_eq!(history[0].vote.no_votes,1);
+		assert_eq!(history[0].rationales,vec![(4,rationale)]);
+	});
+}
+
+#[test]
+fn it_records_rationale_should_fail() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Bhdao::add_contributor(Origin::root(),2));
+		assert_ok!(Bhdao::add_qualifier(Origin::root(),4));
+		assert_ok!(Bhdao::create_document(Origin::signed(2),b"Doc1".to_vec(),b"Test1".to_vec(),b"pdf".to_vec(),b"https://ipfs.hash".to_vec()));
+		assert_ok!(Bhdao::create_qualification_voting(Origin::signed(4),1));
+
+		run_to_block(5);
+
+		let rationale = Rationale { reason: ReasonCode::Incomplete, note: Some(vec![0u8; 65]) };
+		assert_noop!(Bhdao::cast_qualification_vote(Origin::signed(4),1,scorecard(1),Some(rationale)), Error::<Test>::RationaleNoteTooLong);
+	});
+}
+
+#[test]
+fn it_rejects_parameter_changes_from_members_should_fail() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Bhdao::add_qualifier(Origin::root(),4));
+		assert_noop!(Bhdao::set_qualification_quorum(Origin::signed(4),5u32), DispatchError::BadOrigin);
+		assert_noop!(Bhdao::add_contributor(Origin::signed(4),2), DispatchError::BadOrigin);
+	});
+}
+
+#[test]
+fn it_executes_passed_proposal_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Bhdao::add_contributor(Origin::root(),1));
+		assert_ok!(Bhdao::add_qualifier(Origin::root(),4));
+		assert_ok!(Bhdao::set_proposal_voting_window(Origin::root(),100u32));
+
+		let call = Call::Bhdao(crate::Call::set_qualification_quorum { quorum: 5 });
+		let weight = call.get_dispatch_info().weight;
+		assert_ok!(Bhdao::submit_proposal(Origin::signed(1),Box::new(call),0));
+
+		run_to_block(5);
+
+		assert_ok!(Bhdao::cast_proposal_vote(Origin::signed(1),1,true));
+		assert_ok!(Bhdao::cast_proposal_vote(Origin::signed(4),1,true));
+		assert_noop!(Bhdao::cast_proposal_vote(Origin::signed(7),1,true), Error::<Test>::NotAMember);
+		assert_noop!(Bhdao::finalize_proposal_voting(Origin::signed(1),1,weight), Error::<Test>::VoteStillInProgress);
+
+		run_to_block(105);
+
+		assert_ok!(Bhdao::finalize_proposal_voting(Origin::signed(1),1,weight));
+		assert_eq!(Bhdao::get_proposal_vote(1).unwrap().status,VoteStatus::Passed);
+		assert_eq!(Bhdao::get_qualification_quorum(),5u32);
+	});
+}
+
+#[test]
+fn it_schedules_delayed_proposal_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Bhdao::add_contributor(Origin::root(),1));
+		assert_ok!(Bhdao::add_contributor(Origin::root(),2));
+		assert_ok!(Bhdao::set_proposal_voting_window(Origin::root(),100u32));
+
+		let call = Call::Bhdao(crate::Call::set_verification_quorum { quorum: 3 });
+		assert_ok!(Bhdao::submit_proposal(Origin::signed(1),Box::new(call),50));
+
+		run_to_block(5);
+
+		assert_ok!(Bhdao::cast_proposal_vote(Origin::signed(1),1,true));
+		assert_ok!(Bhdao::cast_proposal_vote(Origin::signed(2),1,true));
+
+		run_to_block(105);
+
+		assert_ok!(Bhdao::finalize_proposal_voting(Origin::signed(2),1,0));
+		assert_eq!(Bhdao::get_verification_quorum(),0u32);
+
+		run_to_block(155);
+
+		assert_eq!(Bhdao::get_verification_quorum(),3u32);
+	});
+}
+
+#[test]
+fn it_rejects_proposal_without_majority_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Bhdao::add_contributor(Origin::root(),1));
+		assert_ok!(Bhdao::add_contributor(Origin::root(),2));
+		assert_ok!(Bhdao::set_proposal_voting_window(Origin::root(),100u32));
+
+		let call = Call::Bhdao(crate::Call::set_qualification_quorum { quorum: 5 });
+		assert_ok!(Bhdao::submit_proposal(Origin::signed(1),Box::new(call),0));
+
+		run_to_block(5);
+
+		assert_ok!(Bhdao::cast_proposal_vote(Origin::signed(1),1,true));
+		assert_ok!(Bhdao::cast_proposal_vote(Origin::signed(2),1,false));
+
+		run_to_block(105);
+
+		// Nothing runs, so the weight bound is refunded
+		let info = Bhdao::finalize_proposal_voting(Origin::signed(1),1,1_000_000).unwrap();
+		assert!(info.actual_weight.unwrap() < 1_000_000);
+		assert_eq!(Bhdao::get_proposal_vote(1).unwrap().status,VoteStatus::Failed);
+		assert_eq!(Bhdao::get_qualification_quorum(),0u32);
+	});
+}
+
+#[test]
+fn it_appeals_quorum_failure_should_work() {
+	new_test_ext().execute_with(|| {
+		Balances::make_free_balance_be(&2,10_000);
+		assert_ok!(Bhdao::add_contributor(Origin::root(),2));
+		assert_ok!(Bhdao::add_qualifier(Origin::root(),4));
+		assert_ok!(Bhdao::add_qualifier(Origin::root(),5));
+		assert_ok!(Bhdao::set_qualification_quorum(Origin::root(),1u32));
+		assert_ok!(Bhdao::set_qualification_voting_window(Origin::root(),100u32));
+		assert_ok!(Bhdao::create_document(Origin::signed(2),b"Doc1".to_vec(),b"Test1".to_vec(),b"pdf".to_vec(),b"https://ipfs.hash".to_vec()));
+		assert_ok!(Bhdao::create_qualification_voting(Origin::signed(4),1));
+
+		// Nobody votes
+		run_to_block(105);
+		assert_eq!(Bhdao::get_document(1).unwrap().status,DocumentStatus::QuorumNotReached);
+
+		assert_noop!(Bhdao::appeal_document(Origin::signed(4),1), Error::<Test>::NotDocumentCreator);
+		assert_ok!(Bhdao::appeal_document(Origin::signed(2),1));
+		assert_eq!(Bhdao::get_document(1).unwrap().status,DocumentStatus::UnderReview);
+		assert_eq!(Balances::reserved_balance(&2),1_000);
+		// The appeal round needs one more vote than the default quorum
+		assert_eq!(Bhdao::get_round_quorum(VoteType::Qualification,2),Some(2));
+
+		run_to_block(110);
+		assert_ok!(Bhdao::cast_qualification_vote(Origin::signed(4),2,scorecard(4),None));
+		assert_ok!(Bhdao::cast_qualification_vote(Origin::signed(5),2,scorecard(4),None));
+
+		run_to_block(210);
+		assert_eq!(Bhdao::get_document(1).unwrap().status,DocumentStatus::SuccessfulReview);
+		assert_eq!(Bhdao::get_appeals(1)[0].outcome,Some(VoteStatus::Passed));
+		assert_eq!(Balances::reserved_balance(&2),0);
+		assert_eq!(Balances::free_balance(&2),10_000);
+	});
+}
+
+#[test]
+fn it_appeals_document_should_fail() {
+	new_test_ext().execute_with(|| {
+		Balances::make_free_balance_be(&2,10_000);
+		assert_ok!(Bhdao::add_contributor(Origin::root(),2));
+		assert_ok!(Bhdao::add_qualifier(Origin::root(),4));
+		assert_ok!(Bhdao::set_qualification_voting_window(Origin::root(),100u32));
+		assert_ok!(Bhdao::create_document(Origin::signed(2),b"Doc1".to_vec(),b"Test1".to_vec(),b"pdf".to_vec(),b"https://ipfs.hash".to_vec()));
+		assert_noop!(Bhdao::appeal_document(Origin::signed(2),1), Error::<Test>::DocumentNotRejected);
+		assert_ok!(Bhdao::create_qualification_voting(Origin::signed(4),1));
+
+		run_to_block(10);
+		assert_ok!(Bhdao::cast_qualification_vote(Origin::signed(4),1,scorecard(1),None));
+		run_to_block(105);
+		assert_eq!(Bhdao::get_document(1).unwrap().status,DocumentStatus::Rejected);
+
+		// Two failed appeals slash both deposits, then no more appeals are allowed
+		let mut voting_id = 1;
+		for _ in 0..2 {
+			assert_ok!(Bhdao::appeal_document(Origin::signed(2),1));
+			voting_id += 1;
+			run_to_block(System::block_number() + 5);
+			assert_ok!(Bhdao::cast_qualification_vote(Origin::signed(4),voting_id,scorecard(1),None));
+			run_to_block(System::block_number() + 100);
+		}
+		assert_eq!(Bhdao::get_appeals(1).len(),2);
+		assert_eq!(Balances::free_balance(&2),8_000);
+		assert_noop!(Bhdao::appeal_document(Origin::signed(2),1), Error::<Test>::TooManyAppeals);
+	});
+}
+
+#[test]
+fn it_expires_appeal_round_should_work() {
+	new_test_ext().execute_with(|| {
+		Balances::make_free_balance_be(&2,10_000);
+		assert_ok!(Bhdao::add_contributor(Origin::root(),2));
+		assert_ok!(Bhdao::add_qualifier(Origin::root(),4));
+		assert_ok!(Bhdao::set_qualification_voting_window(Origin::root(),100u32));
+		assert_ok!(Bhdao::create_document(Origin::signed(2),b"Doc1".to_vec(),b"Test1".to_vec(),b"pdf".to_vec(),b"https://ipfs.hash".to_vec()));
+		assert_ok!(Bhdao::create_qualification_voting(Origin::signed(4),1));
+
+		run_to_block(10);
+		assert_ok!(Bhdao::cast_qualification_vote(Origin::signed(4),1,scorecard(1),None));
+		run_to_block(105);
+		assert_eq!(Bhdao::get_document(1).unwrap().status,DocumentStatus::Rejected);
+		assert_ok!(Bhdao::appeal_document(Origin::signed(2),1));
+
+		// The appeal round ends at block 205 and is never finalized
+		System::set_block_number(225);
+		assert_ok!(Bhdao::expire_vote(Origin::signed(9),VoteType::Qualification,2));
+
+		// The rejection stands and the deposit is slashed
+		assert_eq!(Bhdao::get_document(1).unwrap().status,DocumentStatus::Rejected);
+		assert_eq!(Bhdao::get_appeals(1)[0].outcome,Some(VoteStatus::Expired));
+		assert_eq!(Balances::reserved_balance(&2),0);
+		assert_eq!(Balances::free_balance(&2),9_000);
+	});
+}
+
+// Takes a new document by contributor 2 through both voting rounds, using qualifier 4 and
+// contributor 3 as voters. Returns the document id.
+fn create_verified_document() -> u64 {
+	if !Bhdao::ensure_contributor(2) {
+		assert_ok!(Bhdao::add_contributor(Origin::root(),2));
+		assert_ok!(Bhdao::add_contributor(Origin::root(),3));
+		assert_ok!(Bhdao::add_qualifier(Origin::root(),4));
+		assert_ok!(Bhdao::set_qualification_voting_window(Origin::root(),10u32));
+		assert_ok!(Bhdao::set_verification_voting_window(Origin::root(),10u32));
+	}
+	assert_ok!(Bhdao::create_document(Origin::signed(2),b"Doc".to_vec(),b"Test".to_vec(),b"pdf".to_vec(),b"https://ipfs.hash".to_vec()));
+	let document_id = Bhdao::get_total_items();
+
+	assert_ok!(Bhdao::create_qualification_voting(Origin::signed(4),document_id));
+	let voting_id = Bhdao::get_qualification_vote_count();
+	run_to_block(System::block_number() + 1);
+	assert_ok!(Bhdao::cast_qualification_vote(Origin::signed(4),voting_id,scorecard(4),None));
+	run_to_block(System::block_number() + 10);
+
+	assert_ok!(Bhdao::create_verification_voting(Origin::signed(3),document_id));
+	let voting_id = Bhdao::get_verification_vote_count();
+	run_to_block(System::block_number() + 1);
+	assert_ok!(Bhdao::cast_verification_vote(Origin::signed(3),voting_id,true));
+	run_to_block(System::block_number() + 10);
+
+	assert_eq!(Bhdao::get_document(document_id).unwrap().status,DocumentStatus::Verified);
+	document_id
+}
+
+#[test]
+fn it_curates_exhibition_should_work() {
+	new_test_ext().execute_with(|| {
+		let first = create_verified_document();
+		let second = create_verified_document();
+		assert_ok!(Bhdao::add_collector(Origin::root(),8));
+
+		assert_ok!(Bhdao::create_exhibition(Origin::signed(8),b"Harlem".to_vec(),b"Renaissance".to_vec()));
+		assert_ok!(Bhdao::add_exhibit(Origin::signed(8),1,first,b"First".to_vec(),0));
+		assert_ok!(Bhdao::add_exhibit(Origin::signed(8),1,second,b"Second".to_vec(),0));
+		assert_ok!(Bhdao::move_exhibit(Origin::signed(8),1,first,0));
+		assert_ok!(Bhdao::update_exhibition(Origin::signed(8),1,b"Harlem".to_vec(),b"Curated".to_vec(),Some(second)));
+		assert_ok!(Bhdao::set_exhibition_published(Origin::signed(8),1,true));
+
+		let exhibition = Bhdao::get_exhibition(1).unwrap();
+		let order: Vec<u64> = exhibition.exhibits.iter().map(|e| e.document_id).collect();
+		assert_eq!(order,vec![first,second]);
+		assert_eq!(exhibition.cover,Some(second));
+		assert_eq!(Bhdao::get_curator_exhibitions(8),vec![1]);
+		assert_eq!(Bhdao::get_published_exhibitions().len(),1);
+
+		assert_ok!(Bhdao::remove_exhibit(Origin::signed(8),1,second));
+		assert_eq!(Bhdao::get_exhibition(1).unwrap().cover,None);
+	});
+}
+
+#[test]
+fn it_curates_exhibition_should_fail() {
+	new_test_ext().execute_with(|| {
+		let document_id = create_verified_document();
+		assert_ok!(Bhdao::add_collector(Origin::root(),8));
+		assert_ok!(Bhdao::add_collector(Origin::root(),9));
+		assert_ok!(Bhdao::create_document(Origin::signed(2),b"Doc".to_vec(),b"Test".to_vec(),b"pdf".to_vec(),b"https://ipfs.hash".to_vec()));
+
+		assert_noop!(Bhdao::create_exhibition(Origin::signed(2),b"Harlem".to_vec(),Vec::new()), Error::<Test>::NotACollector);
+		assert_ok!(Bhdao::create_exhibition(Origin::signed(8),b"Harlem".to_vec(),Vec::new()));
+		assert_noop!(Bhdao::add_exhibit(Origin::signed(9),1,document_id,Vec::new(),0), Error::<Test>::NotTheCurator);
+		assert_noop!(Bhdao::add_exhibit(Origin::signed(8),1,document_id + 1,Vec::new(),0), Error::<Test>::DocumentNotVerified);
+		assert_ok!(Bhdao::add_exhibit(Origin::signed(8),1,document_id,Vec::new(),0));
+		assert_noop!(Bhdao::add_exhibit(Origin::signed(8),1,document_id,Vec::new(),0), Error::<Test>::ExhibitAlreadyAdded);
+		assert_noop!(Bhdao::update_exhibition(Origin::signed(8),1,b"Harlem".to_vec(),Vec::new(),Some(document_id + 1)), Error::<Test>::CoverNotAnExhibit);
+	});
+}
+
+#[test]
+fn it_blocks_creator_from_qualification_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Bhdao::add_contributor(Origin::root(),2));
+		assert_ok!(Bhdao::add_qualifier(Origin::root(),2));
+		assert_ok!(Bhdao::add_qualifier(Origin::root(),4));
+		assert_ok!(Bhdao::create_document(Origin::signed(2),b"Doc1".to_vec(),b"Test1".to_vec(),b"pdf".to_vec(),b"https://ipfs.hash".to_vec()));
+		assert_noop!(Bhdao::create_qualification_voting(Origin::signed(2),1), Error::<Test>::ConflictOfInterest);
+		assert_ok!(Bhdao::create_qualification_voting(Origin::signed(4),1));
+
+		run_to_block(5);
+
+		assert_noop!(Bhdao::cast_qualification_vote(Origin::signed(2),1,scorecard(4),None), Error::<Test>::ConflictOfInterest);
+	});
+}
+
+#[test]
+fn it_blocks_creator_from_verification_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Bhdao::add_contributor(Origin::root(),2));
+		assert_ok!(Bhdao::add_contributor(Origin::root(),3));
+		assert_ok!(Bhdao::add_qualifier(Origin::root(),4));
+		assert_ok!(Bhdao::set_qualification_voting_window(Origin::root(),10u32));
+		assert_ok!(Bhdao::set_verification_voting_window(Origin::root(),10u32));
+		assert_ok!(Bhdao::create_document(Origin::signed(2),b"Doc1".to_vec(),b"Test1".to_vec(),b"pdf".to_vec(),b"https://ipfs.hash".to_vec()));
+		assert_ok!(Bhdao::create_qualification_voting(Origin::signed(4),1));
+		run_to_block(1);
+		assert_ok!(Bhdao::cast_qualification_vote(Origin::signed(4),1,scorecard(4),None));
+		run_to_block(11);
+
+		assert_noop!(Bhdao::create_verification_voting(Origin::signed(2),1), Error::<Test>::ConflictOfInterest);
+		assert_ok!(Bhdao::create_verification_voting(Origin::signed(3),1));
+		run_to_block(12);
+		assert_noop!(Bhdao::cast_verification_vote(Origin::signed(2),1,true), Error::<Test>::ConflictOfInterest);
+		assert_ok!(Bhdao::cast_verification_vote(Origin::signed(3),1,true));
+
+		// Finalize by hand, ahead of the scheduler
+		System::set_block_number(22);
+		assert_noop!(Bhdao::finalize_verification_voting(Origin::signed(2),1), Error::<Test>::ConflictOfInterest);
+		assert_ok!(Bhdao::finalize_verification_voting(Origin::signed(3),1));
+	});
+}
+
+#[test]
+fn it_declares_conflict_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Bhdao::add_contributor(Origin::root(),2));
+		assert_ok!(Bhdao::add_qualifier(Origin::root(),4));
+		assert_ok!(Bhdao::add_qualifier(Origin::root(),5));
+		assert_ok!(Bhdao::add_qualifier(Origin::root(),6));
+		assert_ok!(Bhdao::set_qualification_quorum(Origin::root(),3u32));
+		assert_ok!(Bhdao::set_qualification_voting_window(Origin::root(),100u32));
+		assert_ok!(Bhdao::create_document(Origin::signed(2),b"Doc1".to_vec(),b"Test1".to_vec(),b"pdf".to_vec(),b"https://ipfs.hash".to_vec()));
+		assert_ok!(Bhdao::create_qualification_voting(Origin::signed(4),1));
+
+		run_to_block(5);
+
+		assert_ok!(Bhdao::cast_qualification_vote(Origin::signed(4),1,scorecard(4),None));
+		assert_noop!(Bhdao::declare_conflict(Origin::signed(4),1), Error::<Test>::MemberAlreadyVoted);
+		assert_ok!(Bhdao::declare_conflict(Origin::signed(6),1));
+		assert_noop!(Bhdao::declare_conflict(Origin::signed(6),1), Error::<Test>::ConflictDeclared);
+		assert_noop!(Bhdao::cast_qualification_vote(Origin::signed(6),1,scorecard(4),None), Error::<Test>::ConflictDeclared);
+		assert_ok!(Bhdao::cast_qualification_vote(Origin::signed(5),1,scorecard(4),None));
+
+		// The recusal lowers the quorum of three to two, which the two votes reach
+		assert_eq!(Bhdao::get_recusal_count(1),1);
+		run_to_block(105);
+		assert_eq!(Bhdao::get_document(1).unwrap().status,DocumentStatus::SuccessfulReview);
+	});
+}
+
+#[test]
+fn it_prunes_finalized_ballots_should_work() {
+	new_test_ext().execute_with(|| {
+		let document_id = create_verified_document();
+		let voting_id = Bhdao::get_verification_vote_count();
+		assert_eq!(Bhdao::get_round_ballots(VoteType::Verification,voting_id),vec![(3,true)]);
+
+		// Ballots are kept for the retention period after finalization
+		let finalized_at = System::block_number();
+		Bhdao::on_idle(finalized_at + 9, 1_000_000);
+		assert_eq!(Bhdao::get_member_vote((VoteType::Verification,voting_id,3)),Some(true));
+
+		Bhdao::on_idle(finalized_at + 10, 1_000_000);
+		assert_eq!(Bhdao::get_member_vote((VoteType::Qualification,1,4)),None);
+		assert_eq!(Bhdao::get_round_ballots(VoteType::Verification,voting_id),vec![]);
+		assert!(Bhdao::get_prune_queue(finalized_at + 10).is_empty());
+		assert_eq!(Bhdao::get_prune_cursor(),finalized_at + 11);
+
+		let summary = Bhdao::get_round_summary(VoteType::Verification,voting_id).unwrap();
+		assert_eq!((summary.yes_votes,summary.no_votes,summary.status),(1,0,VoteStatus::Passed));
+		assert_eq!(Bhdao::get_document(document_id).unwrap().status,DocumentStatus::Verified);
+	});
+}
+
+#[test]
+fn it_migrates_member_votes_should_work() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(0).put::<Bhdao>();
+		let key = (7u64,VoteType::Qualification,3u64).using_encoded(Blake2_128Concat::hash);
+		put_storage_value(b"Bhdao",b"MemberVote",&key,false);
+
+		Migration::<Test>::on_runtime_upgrade();
+
+		assert_eq!(Bhdao::get_member_vote((VoteType::Qualification,3,7)),Some(false));
+		assert_eq!(Bhdao::get_round_ballots(VoteType::Qualification,3),vec![(7,false)]);
+		assert!(get_storage_value::<bool>(b"Bhdao",b"MemberVote",&key).is_none());
+		// The round is closed, so its ballots are queued for pruning
+		assert_eq!(Bhdao::get_prune_queue(10),vec![(VoteType::Qualification,3)]);
+		assert_eq!(Bhdao::on_chain_storage_version(),5);
+	});
+}
+
+#[test]
+fn it_migrates_prune_queue_should_work() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(15);
+		StorageVersion::new(4).put::<Bhdao>();
+		let queue: Vec<(u64,VoteType,u64)> = vec![(2,VoteType::Qualification,1),(8,VoteType::Verification,1)];
+		put_storage_value(b"Bhdao",b"PruneQueue",&[],queue);
+
+		Migration::<Test>::on_runtime_upgrade();
+
+		// Rounds already past their retention period are due in the upgrade block
+		assert_eq!(Bhdao::get_prune_queue(15),vec![(VoteType::Qualification,1)]);
+		assert_eq!(Bhdao::get_prune_queue(18),vec![(VoteType::Verification,1)]);
+		assert_eq!(Bhdao::get_prune_cursor(),15);
+		assert_eq!(Bhdao::on_chain_storage_version(),5);
+	});
+}
+
+#[test]
+fn it_expires_unfinalized_vote_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Bhdao::add_contributor(Origin::root(),2));
+		assert_ok!(Bhdao::add_qualifier(Origin::root(),4));
+		assert_ok!(Bhdao::set_qualification_voting_window(Origin::root(),10u32));
+		assert_ok!(Bhdao::create_document(Origin::signed(2),b"Doc1".to_vec(),b"Test1".to_vec(),b"pdf".to_vec(),b"https://ipfs.hash".to_vec()));
+		run_to_block(1);
+		assert_ok!(Bhdao::create_qualification_voting(Origin::signed(4),1));
+
+		// The round ends at block 11 and can be finalized for another 20 blocks. Blocks are
+		// skipped without running the scheduler, as if its finalization had not gone through.
+		System::set_block_number(31);
+		assert_noop!(Bhdao::expire_vote(Origin::signed(9),VoteType::Qualification,1), Error::<Test>::VoteNotExpired);
+
+		// Anyone can expire it once the grace period is over
+		System::set_block_number(32);
+		assert_ok!(Bhdao::expire_vote(Origin::signed(9),VoteType::Qualification,1));
+		assert_noop!(Bhdao::expire_vote(Origin::signed(9),VoteType::Qualification,1), Error::<Test>::VoteNotInProgress);
+		assert_noop!(Bhdao::finalize_qualification_voting(Origin::signed(4),1), Error::<Test>::VoteNotInProgress);
+		assert_eq!(Bhdao::get_qualification_vote(1).unwrap().status,VoteStatus::Expired);
+		assert_eq!(Bhdao::get_document(1).unwrap().status,DocumentStatus::Submitted);
+
+		// The document can be put to a new round
+		assert_ok!(Bhdao::create_qualification_voting(Origin::signed(4),1));
+	});
+}
+
+#[test]
+fn it_expires_votes_on_initialize_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Bhdao::add_contributor(Origin::root(),1));
+		assert_ok!(Bhdao::set_proposal_voting_window(Origin::root(),100u32));
+		run_to_block(1);
+		let call = Call::Bhdao(crate::Call::set_qualification_quorum { quorum: 5 });
+		assert_ok!(Bhdao::submit_proposal(Origin::signed(1),Box::new(call),0));
+
+		run_to_block(121);
+		assert_eq!(Bhdao::get_proposal_vote(1).unwrap().status,VoteStatus::InProgress);
+
+		run_to_block(122);
+		assert_eq!(Bhdao::get_proposal_vote(1).unwrap().status,VoteStatus::Expired);
+		assert!(Bhdao::get_proposal(1).is_none());
+		assert!(Bhdao::get_expiring_rounds(122).is_empty());
+	});
+}
+
+#[test]
+fn it_draws_review_panel_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Bhdao::add_contributor(Origin::root(),2));
+		assert_ok!(Bhdao::add_qualifier(Origin::root(),4));
+		assert_ok!(Bhdao::add_qualifier(Origin::root(),5));
+		assert_ok!(Bhdao::add_qualifier(Origin::root(),6));
+		assert_ok!(Bhdao::set_review_panel_size(Origin::root(),2u32));
+		assert_ok!(Bhdao::set_qualification_voting_window(Origin::root(),10u32));
+		assert_ok!(Bhdao::create_document(Origin::signed(2),b"Doc1".to_vec(),b"Test1".to_vec(),b"pdf".to_vec(),b"https://ipfs.hash".to_vec()));
+		assert_ok!(Bhdao::create_qualification_voting(Origin::signed(4),1));
+
+		// The panel is drawn five blocks later, and nobody can vote until then
+		assert_eq!(Bhdao::get_review_panel(1),None);
+		run_to_block(2);
+		assert_noop!(Bhdao::cast_qualification_vote(Origin::signed(4),1,scorecard(4),None), Error::<Test>::PanelNotDrawn);
+		assert_noop!(Bhdao::draw_review_panel(Origin::signed(9),1), Error::<Test>::PanelNotDue);
+
+		run_to_block(5);
+		assert_noop!(Bhdao::draw_review_panel(Origin::signed(9),1), Error::<Test>::NoPendingPanel);
+		let panel = Bhdao::get_review_panel(1).unwrap();
+		assert_eq!(panel.len(),2);
+		let outsider = [4, 5, 6].into_iter().find(|q| !panel.contains(q)).unwrap();
+		assert_eq!(Bhdao::get_workload(panel[0]),1);
+		assert_eq!(Bhdao::get_workload(outsider),0);
+
+		run_to_block(6);
+		assert_noop!(Bhdao::cast_qualification_vote(Origin::signed(outsider),1,scorecard(4),None), Error::<Test>::NotOnReviewPanel);
+		assert_ok!(Bhdao::cast_qualification_vote(Origin::signed(panel[0]),1,scorecard(4),None));
+		assert_ok!(Bhdao::cast_qualification_vote(Origin::signed(panel[1]),1,scorecard(4),None));
+
+		run_to_block(16);
+		assert_eq!(Bhdao::get_document(1).unwrap().status,DocumentStatus::SuccessfulReview);
+		assert_eq!(Bhdao::get_workload(panel[0]),0);
+		assert_eq!(Bhdao::get_workload(panel[1]),0);
+	});
+}
+
+#[test]
+fn it_limits_reviewer_workload_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Bhdao::add_contributor(Origin::root(),2));
+		assert_ok!(Bhdao::add_qualifier(Origin::root(),4));
+		assert_ok!(Bhdao::add_qualifier(Origin::root(),5));
+		assert_ok!(Bhdao::set_review_panel_size(Origin::root(),2u32));
+		assert_ok!(Bhdao::set_qualification_voting_window(Origin::root(),10u32));
+		assert_ok!(Bhdao::create_document(Origin::signed(2),b"Doc1".to_vec(),b"Test1".to_vec(),b"pdf".to_vec(),b"https://ipfs.hash".to_vec()));
+		assert_ok!(Bhdao::create_document(Origin::signed(2),b"Doc2".to_vec(),b"Test2".to_vec(),b"pdf".to_vec(),b"https://ipfs.hash".to_vec()));
+		assert_ok!(Bhdao::create_qualification_voting(Origin::signed(4),1));
+		assert_ok!(Bhdao::create_qualification_voting(Origin::signed(4),2));
+
+		// Both qualifiers are drawn to the first panel, which is as many as they may hold, so
+		// the second round expires when its panel is due
+		run_to_block(5);
+		assert_eq!(Bhdao::get_review_panel(1).unwrap().len(),2);
+		assert_eq!(Bhdao::get_qualification_vote(2).unwrap().status,VoteStatus::Expired);
+		assert_eq!(Bhdao::get_document(2).unwrap().status,DocumentStatus::Submitted);
+		assert_noop!(Bhdao::create_qualification_voting(Origin::signed(4),2), Error::<Test>::NotEnoughReviewers);
+
+		run_to_block(16);
+		assert_ok!(Bhdao::create_qualification_voting(Origin::signed(5),2));
+	});
+}
+
+#[test]
+fn it_qualifies_on_mean_scores_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Bhdao::add_contributor(Origin::root(),2));
+		assert_ok!(Bhdao::add_qualifier(Origin::root(),4));
+		assert_ok!(Bhdao::add_qualifier(Origin::root(),5));
+		assert_ok!(Bhdao::add_qualifier(Origin::root(),6));
+		assert_ok!(Bhdao::set_qualification_voting_window(Origin::root(),10u32));
+		assert_ok!(Bhdao::create_document(Origin::signed(2),b"Doc1".to_vec(),b"Test1".to_vec(),b"pdf".to_vec(),b"https://ipfs.hash".to_vec()));
+		assert_ok!(Bhdao::create_qualification_voting(Origin::signed(4),1));
+
+		run_to_block(2);
+		// No ballot meets every threshold on its own, but each criterion does on average
+		let first = Scorecard { authenticity: 5, relevance: 5, completeness: 5, originality: 2 };
+		let second = Scorecard { authenticity: 5, relevance: 4, completeness: 4, originality: 2 };
+		let third = Scorecard { authenticity: 2, relevance: 3, completeness: 3, originality: 5 };
+		assert_ok!(Bhdao::cast_qualification_vote(Origin::signed(4),1,first,None));
+		assert_ok!(Bhdao::cast_qualification_vote(Origin::signed(5),1,second,None));
+		assert_ok!(Bhdao::cast_qualification_vote(Origin::signed(6),1,third,None));
+		assert_eq!(Bhdao::get_qualification_vote(1).unwrap().no_votes,3);
+
+		run_to_block(12);
+		let document = Bhdao::get_document(1).unwrap();
+		assert_eq!(document.status,DocumentStatus::SuccessfulReview);
+		assert_eq!(document.scores,Some(Scorecard { authenticity: 4, relevance: 4, completeness: 4, originality: 3 }));
+	});
+}
+
+#[test]
+fn it_scores_document_should_fail() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Bhdao::add_contributor(Origin::root(),2));
+		assert_ok!(Bhdao::add_qualifier(Origin::root(),4));
+		assert_ok!(Bhdao::set_qualification_voting_window(Origin::root(),10u32));
+		assert_noop!(Bhdao::set_score_thresholds(Origin::root(),scorecard(6)), Error::<Test>::ScoreOutOfRange);
+		assert_ok!(Bhdao::set_score_thresholds(Origin::root(),scorecard(5)));
+		assert_ok!(Bhdao::create_document(Origin::signed(2),b"Doc1".to_vec(),b"Test1".to_vec(),b"pdf".to_vec(),b"https://ipfs.hash".to_vec()));
+		assert_ok!(Bhdao::create_qualification_voting(Origin::signed(4),1));
+
+		run_to_block(2);
+		assert_noop!(Bhdao::cast_qualification_vote(Origin::signed(4),1,scorecard(6),None), Error::<Test>::ScoreOutOfRange);
+		assert_ok!(Bhdao::cast_qualification_vote(Origin::signed(4),1,scorecard(4),None));
+
+		run_to_block(12);
+		let document = Bhdao::get_document(1).unwrap();
+		assert_eq!(document.status,DocumentStatus::Rejected);
+		assert_eq!(document.scores,Some(scorecard(4)));
+	});
+}
+
+#[test]
+fn it_migrates_documents_should_work() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(1).put::<Bhdao>();
+		let old_document = (2u64,b"Doc1".to_vec(),b"Test1".to_vec(),b"pdf".to_vec(),b"https://ipfs.hash".to_vec(),DocumentStatus::Verified);
+		put_storage_value(b"Bhdao",b"Documents",&1u64.using_encoded(Blake2_128Concat::hash),old_document);
+
+		Migration::<Test>::on_runtime_upgrade();
+
+		let document = Bhdao::get_document(1).unwrap();
+		assert_eq!(document.title,b"Doc1".to_vec());
+		assert_eq!(document.status,DocumentStatus::Verified);
+		assert_eq!(document.scores,None);
+		assert_eq!(Bhdao::on_chain_storage_version(),5);
+	});
+}
+
+#[test]
+fn it_migrates_recusals_should_work() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(2).put::<Bhdao>();
+		crate::pallet::Recusals::<Test>::insert(1,4,());
+		crate::pallet::Recusals::<Test>::insert(1,5,());
+		crate::pallet::Recusals::<Test>::insert(2,4,());
+
+		Migration::<Test>::on_runtime_upgrade();
+
+		assert_eq!(Bhdao::get_recusal_count(1),2);
+		assert_eq!(Bhdao::get_recusal_count(2),1);
+		assert_eq!(Bhdao::on_chain_storage_version(),5);
+	});
+}
+
+#[test]
+fn it_migrates_score_totals_should_work() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(3).put::<Bhdao>();
+		crate::pallet::Scorecards::<Test>::insert(1,4,scorecard(4));
+		crate::pallet::Scorecards::<Test>::insert(1,5,scorecard(1));
+
+		Migration::<Test>::on_runtime_upgrade();
+
+		assert_eq!(Bhdao::get_score_totals(1),([5,5,5,5],2));
+		assert_eq!(Bhdao::mean_scores(1),Some(scorecard(2)));
+		assert_eq!(Bhdao::on_chain_storage_version(),5);
+	});
+}
+
+#[test]
+fn it_revokes_flagged_document_should_work() {
+	new_test_ext().execute_with(|| {
+		let document_id = create_verified_document();
+		assert_ok!(Bhdao::add_contributor(Origin::root(),5));
+		Balances::make_free_balance_be(&3,10_000);
+		Balances::make_free_balance_be(&4,10_000);
+
+		assert_ok!(Bhdao::flag_document(Origin::signed(3),document_id,FlagReason::Forged,Some(b"QmEvidence".to_vec())));
+		assert_noop!(Bhdao::flag_document(Origin::signed(3),document_id,FlagReason::Forged,None), Error::<Test>::AlreadyFlagged);
+		assert_eq!(Bhdao::get_active_review(document_id),None);
+
+		// The second flag reaches the threshold and opens a review
+		assert_ok!(Bhdao::flag_document(Origin::signed(4),document_id,FlagReason::Privacy,None));
+		assert_eq!(Balances::reserved_balance(&4),500);
+		assert_eq!(Bhdao::get_active_review(document_id),Some(1));
+		assert_noop!(Bhdao::flag_document(Origin::signed(5),document_id,FlagReason::Legal,None), Error::<Test>::ReviewInProgress);
+
+		run_to_block(System::block_number() + 1);
+		assert_noop!(Bhdao::cast_review_vote(Origin::signed(2),1,false), Error::<Test>::ConflictOfInterest);
+		assert_ok!(Bhdao::cast_review_vote(Origin::signed(3),1,true));
+		assert_ok!(Bhdao::cast_review_vote(Origin::signed(5),1,true));
+		run_to_block(System::block_number() + 10);
+		assert_ok!(Bhdao::finalize_review_voting(Origin::signed(5),1));
+
+		// One flag alleges forgery, so the document is revoked rather than quarantined
+		assert_eq!(Bhdao::get_document(document_id).unwrap().status,DocumentStatus::Revoked);
+		assert_eq!(Balances::free_balance(&3),10_000);
+		assert_eq!(Balances::free_balance(&4),10_000);
+		let flags = Bhdao::get_flags(document_id);
+		assert!(flags.iter().all(|flag| flag.review == Some(1) && flag.outcome == Some(VoteStatus::Passed)));
+		assert_eq!(Bhdao::get_document_history(document_id).last().unwrap().vote_type,VoteType::Review);
+	});
+}
+
+#[test]
+fn it_keeps_document_verified_on_failed_review_should_work() {
+	new_test_ext().execute_with(|| {
+		let document_id = create_verified_document();
+		assert_ok!(Bhdao::add_contributor(Origin::root(),5));
+		Balances::make_free_balance_be(&3,10_000);
+		Balances::make_free_balance_be(&4,10_000);
+		assert_noop!(Bhdao::flag_document(Origin::signed(9),document_id,FlagReason::Legal,None), Error::<Test>::NotAMember);
+
+		assert_ok!(Bhdao::flag_document(Origin::signed(3),document_id,FlagReason::Legal,None));
+		assert_ok!(Bhdao::flag_document(Origin::signed(4),document_id,FlagReason::Legal,None));
+
+		run_to_block(System::block_number() + 1);
+		assert_ok!(Bhdao::cast_review_vote(Origin::signed(3),1,false));
+		assert_ok!(Bhdao::cast_review_vote(Origin::signed(5),1,false));
+		run_to_block(System::block_number() + 10);
+		assert_ok!(Bhdao::finalize_review_voting(Origin::signed(5),1));
+
+		// The flags were rejected, so their deposits are slashed and the document can be flagged again
+		assert_eq!(Bhdao::get_document(document_id).unwrap().status,DocumentStatus::Verified);
+		assert_eq!(Balances::free_balance(&3),9_500);
+		assert_eq!(Balances::free_balance(&4),9_500);
+		assert_eq!(Bhdao::get_flags(document_id)[0].outcome,Some(VoteStatus::Failed));
+		assert_ok!(Bhdao::flag_document(Origin::signed(3),document_id,FlagReason::Forged,None));
+		assert_eq!(Bhdao::get_flags(document_id).len(),3);
+	});
+}
+
+#[test]
+fn it_finalizes_rounds_through_scheduler_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Bhdao::add_contributor(Origin::root(),2));
+		assert_ok!(Bhdao::add_qualifier(Origin::root(),4));
+		assert_ok!(Bhdao::set_qualification_voting_window(Origin::root(),10u32));
+		assert_ok!(Bhdao::create_document(Origin::signed(2),b"Doc1".to_vec(),b"Test1".to_vec(),b"pdf".to_vec(),b"https://ipfs.hash".to_vec()));
+		run_to_block(1);
+		assert_ok!(Bhdao::create_qualification_voting(Origin::signed(4),1));
+		assert_eq!(Bhdao::get_round_finalization(VoteType::Qualification,1),Some(12));
+
+		run_to_block(2);
+		assert_ok!(Bhdao::cast_qualification_vote(Origin::signed(4),1,scorecard(4),None));
+		assert_noop!(Bhdao::finalize_round(Origin::signed(4),VoteType::Qualification,1), DispatchError::BadOrigin);
+
+		// The round ends at block 11 and is finalized at the start of block 12
+		run_to_block(11);
+		assert_eq!(Bhdao::get_qualification_vote(1).unwrap().status,VoteStatus::InProgress);
+		run_to_block(12);
+		assert_eq!(Bhdao::get_qualification_vote(1).unwrap().status,VoteStatus::Passed);
+		assert_eq!(Bhdao::get_document(1).unwrap().status,DocumentStatus::SuccessfulReview);
+		assert_eq!(Bhdao::get_round_finalization(VoteType::Qualification,1),None);
+	});
+}
+
+#[test]
+fn it_spreads_round_finalization_over_blocks_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Bhdao::add_contributor(Origin::root(),2));
+		assert_ok!(Bhdao::add_qualifier(Origin::root(),4));
+		assert_ok!(Bhdao::set_qualification_voting_window(Origin::root(),10u32));
+		for _ in 0..3 {
+			assert_ok!(Bhdao::create_document(Origin::signed(2),b"Doc1".to_vec(),b"Test1".to_vec(),b"pdf".to_vec(),b"https://ipfs.hash".to_vec()));
+		}
+		run_to_block(1);
+
+		// Two rounds fit in block 12, so the third one is finalized in block 13
+		for document_id in 1..4 {
+			assert_ok!(Bhdao::create_qualification_voting(Origin::signed(4),document_id));
+		}
+		assert_eq!(Bhdao::get_round_finalization(VoteType::Qualification,2),Some(12));
+		assert_eq!(Bhdao::get_round_finalization(VoteType::Qualification,3),Some(13));
+		assert_eq!(Bhdao::get_rounds_scheduled_at(12),2);
+
+		// Concluding a round frees its seat
+		System::set_block_number(32);
+		assert_ok!(Bhdao::expire_vote(Origin::signed(9),VoteType::Qualification,1));
+		assert_eq!(Bhdao::get_rounds_scheduled_at(12),1);
+	});
+}
+
+#[test]
+fn it_reports_unscheduled_round_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Bhdao::add_contributor(Origin::root(),2));
+		assert_ok!(Bhdao::add_qualifier(Origin::root(),4));
+		assert_ok!(Bhdao::set_qualification_voting_window(Origin::root(),10u32));
+		for _ in 0..21 {
+			assert_ok!(Bhdao::create_document(Origin::signed(2),b"Doc1".to_vec(),b"Test1".to_vec(),b"pdf".to_vec(),b"https://ipfs.hash".to_vec()));
+		}
+		run_to_block(1);
+
+		// Twenty rounds fill every block they can be scheduled in, so the last one is reported
+		for document_id in 1..22 {
+			assert_ok!(Bhdao::create_qualification_voting(Origin::signed(4),document_id));
+		}
+		assert_eq!(Bhdao::get_round_finalization(VoteType::Qualification,20),Some(21));
+		assert_eq!(Bhdao::get_round_finalization(VoteType::Qualification,21),None);
+		System::assert_has_event(Event::Bhdao(crate::Event::RoundNotScheduled(VoteType::Qualification,21)));
+	});
+}
+
+#[test]
+fn it_cancels_scheduled_finalization_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Bhdao::add_contributor(Origin::root(),2));
+		assert_ok!(Bhdao::add_qualifier(Origin::root(),4));
+		assert_ok!(Bhdao::set_qualification_voting_window(Origin::root(),10u32));
+		assert_ok!(Bhdao::create_document(Origin::signed(2),b"Doc1".to_vec(),b"Test1".to_vec(),b"pdf".to_vec(),b"https://ipfs.hash".to_vec()));
+		run_to_block(1);
+		assert_ok!(Bhdao::create_qualification_voting(Origin::signed(4),1));
+
+		// A member finalizes the round before the scheduler gets to it
+		System::set_block_number(12);
+		assert_ok!(Bhdao::finalize_qualification_voting(Origin::signed(4),1));
+		assert_eq!(Bhdao::get_round_finalization(VoteType::Qualification,1),None);
+		assert_eq!(Bhdao::get_document(1).unwrap().status,DocumentStatus::Rejected);
+	});
+}
+
+#[test]
+fn it_checks_state_invariants_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Bhdao::add_contributor(Origin::root(),2));
+		assert_ok!(Bhdao::add_qualifier(Origin::root(),4));
+		assert_ok!(Bhdao::set_qualification_voting_window(Origin::root(),10u32));
+		assert_ok!(Bhdao::create_document(Origin::signed(2),b"Doc1".to_vec(),b"Test1".to_vec(),b"pdf".to_vec(),b"https://ipfs.hash".to_vec()));
+		run_to_block(1);
+		assert_ok!(Bhdao::create_qualification_voting(Origin::signed(4),1));
+		assert_ok!(Bhdao::do_try_state());
+
+		run_to_block(12);
+		assert_eq!(Bhdao::get_document(1).unwrap().status,DocumentStatus::Rejected);
+		assert_ok!(Bhdao::do_try_state());
+		assert_ok!(Nft::do_try_state());
+	});
+}
+
+#[test]
+fn it_checks_state_invariants_should_fail() {
+	new_test_ext().execute_with(|| {
+		// A role token minted outside the DAO belongs to no member
+		assert_ok!(Nft::mint(Origin::root(),Roles::QualifierRole as u32,4));
+		assert_eq!(Bhdao::do_try_state(),Err("role token holder is not a member"));
+	});
+}
+
+#[test]
+fn it_waives_voting_fees_within_quota_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Bhdao::add_contributor(Origin::root(),2));
+		run_to_block(1);
+		let vote = Call::Bhdao(crate::Call::cast_verification_vote { voting_id: 1, vote_cast: true });
+		let other = Call::Bhdao(crate::Call::create_verification_voting { document_id: 1 });
+		let info = Default::default();
+		let waive = || WaiveVotingFees::<Test, frame_system::CheckNonZeroSender<Test>>::from(frame_system::CheckNonZeroSender::new());
+		assert_eq!(WaiveVotingFees::<Test, frame_system::CheckNonZeroSender<Test>>::IDENTIFIER,"CheckNonZeroSender");
+
+		// Other calls and non-members go through the wrapped extension
+		assert_eq!(waive().pre_dispatch(&2,&other,&info,0),Ok(Some(())));
+		assert_eq!(waive().pre_dispatch(&3,&vote,&info,0),Ok(Some(())));
+
+		// Two free votes per era, each taking its own slot in the pool
+		let validity = waive().validate(&2,&vote,&info,0).unwrap();
+		assert_eq!(validity.priority,FREE_CALL_PRIORITY);
+		assert_eq!(validity.provides,vec![("WaiveVotingFees",(2u64,0u64,0u32)).encode()]);
+		assert_eq!(validity.longevity,9);
+		assert_eq!(waive().pre_dispatch(&2,&vote,&info,0),Ok(None));
+		assert_eq!(waive().validate(&2,&vote,&info,0).unwrap().provides,vec![("WaiveVotingFees",(2u64,0u64,1u32)).encode()]);
+		assert_eq!(waive().pre_dispatch(&2,&vote,&info,0),Ok(None));
+
+		// Past the quota the wrapped extension validates and charges
+		assert!(waive().validate(&2,&vote,&info,0).unwrap().provides.is_empty());
+		assert_eq!(waive().pre_dispatch(&2,&vote,&info,0),Ok(Some(())));
+		assert_eq!(Bhdao::get_free_calls(2),(0,2));
+
+		// The quota is restored in the next era
+		run_to_block(10);
+		assert_eq!(waive().pre_dispatch(&2,&vote,&info,0),Ok(None));
+		assert_eq!(Bhdao::get_free_calls(2),(1,1));
+	});
+}
+
+#[test]
+fn it_records_member_activity_should_work() {
+	new_test_ext().execute_with(|| {
+		create_verified_document();
+
+		let creator = Bhdao::get_member_profile(2).unwrap();
+		assert_eq!(creator.roles,vec![Roles::ContributorRole]);
+		assert_eq!(creator.transactions,1);
+		assert_eq!(creator.activity,Activity { documents_submitted: 1, documents_verified: 1, votes_cast: 0, winning_votes: 0 });
+
+		// The qualifier opened the round and voted with the outcome
+		let qualifier = Bhdao::get_member_profile(4).unwrap();
+		assert_eq!(qualifier.transactions,2);
+		assert_eq!(qualifier.activity,Activity { documents_submitted: 0, documents_verified: 0, votes_cast: 1, winning_votes: 1 });
+
+		assert_eq!(Bhdao::get_total_transactions(),5);
+		assert_eq!(Bhdao::get_member_profile(7),None);
+
+		// Failed calls are not counted
+		assert_noop!(Bhdao::cast_verification_vote(Origin::signed(3),1,true), Error::<Test>::MemberAlreadyVoted);
+		assert_eq!(Bhdao::get_transactions_per_address(3),Some(2));
+	});
+}
+
+#[test]
+fn it_administers_dao_with_key_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Bhdao::set_key(Origin::root(),9));
+		assert_eq!(Bhdao::get_key(),Some(9));
+
+		// The key holder manages membership and parameters without root
+		assert_ok!(Bhdao::add_qualifier(Origin::signed(9),4));
+		assert_ok!(Bhdao::set_qualification_quorum(Origin::signed(9),5u32));
+		assert_eq!(Bhdao::get_qualification_quorum(),5);
+
+		// and hands the key over
+		assert_ok!(Bhdao::set_key(Origin::signed(9),8));
+		assert_noop!(Bhdao::add_contributor(Origin::signed(9),2), DispatchError::BadOrigin);
+		assert_ok!(Bhdao::add_contributor(Origin::signed(8),2));
+	});
+}
+
+#[test]
+fn it_sets_key_should_fail() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(Bhdao::set_key(Origin::signed(9),9), DispatchError::BadOrigin);
+		assert_eq!(Bhdao::get_key(),None);
+	});
+}
+
+#[test]
+fn it_adds_members_in_batch_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Bhdao::add_contributor(Origin::root(),1));
+		assert_ok!(Bhdao::add_members(Origin::root(),vec![(3,Roles::ContributorRole),(2,Roles::ContributorRole),(4,Roles::QualifierRole)]));
+
+		assert_eq!(Bhdao::get_all_contributors(),vec![1,2,3]);
+		assert_eq!(Bhdao::contributors_uid_count(),3);
+		assert_eq!(Bhdao::get_all_qualifiers(),vec![4]);
+		assert_eq!(Nft::get_active_tokens(Roles::ContributorRole as u32),3);
+		assert!(Nft::token_of(Roles::QualifierRole as u32,&4).is_some());
+		assert_ok!(Bhdao::do_try_state());
+	});
+}
+
+#[test]
+fn it_adds_members_in_batch_should_fail() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Bhdao::add_contributor(Origin::root(),1));
+		assert_noop!(Bhdao::add_members(Origin::signed(1),vec![(2,Roles::ContributorRole)]), DispatchError::BadOrigin);
+		assert_noop!(
+			Bhdao::add_members(Origin::root(),vec![(2,Roles::ContributorRole),(3,Roles::ContributorRole),(4,Roles::ContributorRole),(5,Roles::ContributorRole)]),
+			Error::<Test>::BatchTooLarge
+		);
+		// One existing member rejects the whole batch
+		assert_noop!(
+			Bhdao::add_members(Origin::root(),vec![(2,Roles::ContributorRole),(1,Roles::ContributorRole)]),
+			Error::<Test>::ContributorAlreadyExists
+		);
+		assert_noop!(
+			Bhdao::add_members(Origin::root(),vec![(2,Roles::QualifierRole),(2,Roles::QualifierRole)]),
+			Error::<Test>::QualifierAlreadyExists
+		);
+	});
+}
+
+#[test]
+fn it_changes_role_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Bhdao::add_members(Origin::root(),vec![(2,Roles::ContributorRole),(3,Roles::ContributorRole)]));
+		assert_ok!(Bhdao::change_role(Origin::root(),2,Roles::ContributorRole,Roles::QualifierRole));
+
+		assert_eq!(Bhdao::get_all_contributors(),vec![3]);
+		assert_eq!(Bhdao::get_all_qualifiers(),vec![2]);
+		assert_eq!(Nft::token_of(Roles::ContributorRole as u32,&2),None);
+		assert!(Nft::token_of(Roles::QualifierRole as u32,&2).is_some());
+		assert_ok!(Bhdao::do_try_state());
+
+		// Uids are not reused once a member leaves a role
+		assert_eq!(Bhdao::contributors_uid_count(),2);
+		assert_eq!(Bhdao::qualifiers_uid_count(),1);
+		assert_ok!(Bhdao::add_contributor(Origin::root(),4));
+		assert_eq!(Bhdao::contributors_uid_count(),3);
+
+		assert_noop!(Bhdao::change_role(Origin::root(),2,Roles::QualifierRole,Roles::QualifierRole), Error::<Test>::SameRole);
+		assert_noop!(Bhdao::change_role(Origin::root(),2,Roles::ContributorRole,Roles::CollectorRole), Error::<Test>::NotAContributor);
+		assert_ok!(Bhdao::add_members(Origin::root(),vec![(3,Roles::QualifierRole)]));
+		assert_noop!(Bhdao::change_role(Origin::root(),3,Roles::ContributorRole,Roles::QualifierRole), Error::<Test>::QualifierAlreadyExists);
+	});
+}
+
+#[test]
+fn it_raises_role_cap_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(Nft::get_collection(Roles::CollectorRole as u32).unwrap().total_supply,100);
+
+		assert_ok!(Bhdao::set_role_cap(Origin::root(),Roles::CollectorRole,150));
+		assert_eq!(Nft::get_collection(Roles::CollectorRole as u32).unwrap().total_supply,150);
+	});
+}
+
+#[test]
+fn it_caps_votes_per_round_should_fail() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Bhdao::add_contributor(Origin::root(),2));
+		for qualifier in 3..7 {
+			assert_ok!(Bhdao::add_qualifier(Origin::root(),qualifier));
+		}
+		assert_ok!(Bhdao::create_document(Origin::signed(2),b"Doc1".to_vec(),b"Test1".to_vec(),b"pdf".to_vec(),b"https://ipfs.hash".to_vec()));
+		assert_ok!(Bhdao::create_qualification_voting(Origin::signed(3),1));
+
+		run_to_block(2);
+		for qualifier in 3..6 {
+			assert_ok!(Bhdao::cast_qualification_vote(Origin::signed(qualifier),1,scorecard(4),None));
+		}
+		assert_noop!(Bhdao::cast_qualification_vote(Origin::signed(6),1,scorecard(4),None), Error::<Test>::TooManyVotes);
+	});
+}
+
+#[test]
+fn it_refunds_unused_finalize_weight_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Bhdao::add_contributor(Origin::root(),2));
+		assert_ok!(Bhdao::add_qualifier(Origin::root(),4));
+		assert_ok!(Bhdao::set_qualification_voting_window(Origin::root(),10u32));
+		assert_ok!(Bhdao::create_document(Origin::signed(2),b"Doc1".to_vec(),b"Test1".to_vec(),b"pdf".to_vec(),b"https://ipfs.hash".to_vec()));
+		assert_ok!(Bhdao::create_qualification_voting(Origin::signed(4),1));
+
+		run_to_block(2);
+		assert_ok!(Bhdao::cast_qualification_vote(Origin::signed(4),1,scorecard(4),None));
+
+		// Finalize by hand, ahead of the scheduler
+		System::set_block_number(11);
+		let call = crate::Call::<Test>::finalize_round { vote_type: VoteType::Qualification, voting_id: 1 };
+		let declared = call.get_dispatch_info().weight;
+		let post_info = Bhdao::finalize_round(Origin::root(),VoteType::Qualification,1).unwrap();
+		assert_eq!(post_info.actual_weight,Some(declared - Bhdao::finalize_weight(3,1_000) + Bhdao::finalize_weight(1,0)));
+	});
+}
+
+#[test]
+fn it_initializes_collections_should_fail() {
+	bare_test_ext().execute_with(|| {
+		assert_noop!(Bhdao::set_role_cap(Origin::root(),Roles::CollectorRole,150), pallet_nft::Error::<Test>::CollectionDoesNotExist);
+		assert_ok!(Bhdao::init_collections(Origin::root()));
+		assert_noop!(Bhdao::init_collections(Origin::root()), pallet_nft::Error::<Test>::CollectionExists);
+		assert_noop!(Bhdao::set_role_cap(Origin::root(),Roles::CollectorRole,100), Error::<Test>::RoleCapNotRaised);
+		assert_noop!(Bhdao::set_role_cap(Origin::root(),Roles::CollectorRole,1_001), Error::<Test>::RoleCapTooHigh);
+		assert_noop!(Bhdao::set_role_cap(Origin::signed(1),Roles::CollectorRole,150), DispatchError::BadOrigin);
+	});
+}
+
+#[test]
+fn it_suspends_member_with_frozen_badge_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Bhdao::add_contributor(Origin::root(),2));
+		let token = Nft::token_of(Roles::ContributorRole as u32,&2).unwrap();
+
+		assert_ok!(Nft::freeze(Origin::root(),Roles::ContributorRole as u32,token));
+		assert!(!Bhdao::ensure_contributor(2));
+		assert_noop!(
+			Bhdao::create_document(Origin::signed(2),b"Doc1".to_vec(),b"Test1".to_vec(),b"pdf".to_vec(),b"https://ipfs.hash".to_vec()),
+			Error::<Test>::NotAContributor
+		);
+		assert_eq!(Bhdao::get_member_profile(2).unwrap().roles,vec![Roles::ContributorRole]);
+
+		assert_ok!(Nft::thaw(Origin::root(),Roles::ContributorRole as u32,token));
+		assert_ok!(Bhdao::create_document(Origin::signed(2),b"Doc1".to_vec(),b"Test1".to_vec(),b"pdf".to_vec(),b"https://ipfs.hash".to_vec()));
+	});
+}
+
+#[test]
+fn it_revokes_member_with_burned_badge_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Bhdao::add_contributor(Origin::root(),2));
+		let token = Nft::token_of(Roles::ContributorRole as u32,&2).unwrap();
+		assert_ok!(Nft::freeze(Origin::root(),Roles::ContributorRole as u32,token));
+
+		// A suspended member cannot burn their badge to lift the suspension
+		assert_noop!(Nft::burn(Origin::signed(2),Roles::ContributorRole as u32,token), pallet_nft::Error::<Test>::Frozen);
+
+		// Burning it through the collection owner revokes the role
+		assert_ok!(Nft::force_burn(Origin::root(),Roles::ContributorRole as u32,token));
+		assert!(!Bhdao::ensure_contributor(2));
+		assert_noop!(
+			Bhdao::create_document(Origin::signed(2),b"Doc1".to_vec(),b"Test1".to_vec(),b"pdf".to_vec(),b"https://ipfs.hash".to_vec()),
+			Error::<Test>::NotAContributor
+		);
+		assert_ok!(Bhdao::do_try_state());
+		assert_ok!(Nft::do_try_state());
+	});
+}
+
+#[test]
+fn it_reissues_badge_should_work() {
+	new_test_ext().execute_with(|| {
+		// A member left without a badge, as the old add calls allowed
+		assert_ok!(Bhdao::add_contributor(Origin::root(),2));
+		let token = Nft::token_of(Roles::ContributorRole as u32,&2).unwrap();
+		assert_ok!(Nft::force_burn(Origin::root(),Roles::ContributorRole as u32,token));
+		assert!(!Bhdao::ensure_contributor(2));
+
+		assert_noop!(Bhdao::reissue_badge(Origin::signed(2),2,Roles::ContributorRole), DispatchError::BadOrigin);
+		assert_noop!(Bhdao::reissue_badge(Origin::root(),3,Roles::ContributorRole), Error::<Test>::NotAContributor);
+		assert_ok!(Bhdao::reissue_badge(Origin::root(),2,Roles::ContributorRole));
+		assert!(Bhdao::ensure_contributor(2));
+		assert_noop!(Bhdao::reissue_badge(Origin::root(),2,Roles::ContributorRole), Error::<Test>::BadgeAlreadyHeld);
+		assert_ok!(Bhdao::do_try_state());
+	});
+}
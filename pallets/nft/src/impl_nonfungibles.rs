@@ -0,0 +1,98 @@
+//! Implementations of the `nonfungibles` traits, so other pallets can use collections and tokens
+//! without depending on `pallet_nft::Config`. Classes are collections and instances are tokens.
+
+use super::*;
+use frame_support::{
+	ensure,
+	traits::tokens::nonfungibles::{Create, Inspect, InspectEnumerable, Mutate, Transfer},
+};
+use sp_runtime::{traits::Zero, ArithmeticError, DispatchResult};
+use sp_std::prelude::*;
+
+impl<T: Config> Inspect<T::AccountId> for Pallet<T> {
+	type InstanceId = u32;
+	type ClassId = u32;
+
+	fn owner(class: &Self::ClassId, instance: &Self::InstanceId) -> Option<T::AccountId> {
+		Tokens::<T>::get(class, instance).map(|token| token.owner)
+	}
+
+	fn class_owner(class: &Self::ClassId) -> Option<T::AccountId> {
+		Collections::<T>::get(class).and_then(|collection| collection.owner)
+	}
+
+	fn attribute(class: &Self::ClassId, instance: &Self::InstanceId, key: &[u8]) -> Option<Vec<u8>> {
+		Attributes::<T>::get((class, Some(instance), key)).map(|(value, _)| value)
+	}
+
+	fn class_attribute(class: &Self::ClassId, key: &[u8]) -> Option<Vec<u8>> {
+		Attributes::<T>::get((class, None::<u32>, key)).map(|(value, _)| value)
+	}
+
+	/// Tokens can be transferred if their collection is transferable and they are not frozen.
+	fn can_transfer(class: &Self::ClassId, instance: &Self::InstanceId) -> bool {
+		match Collections::<T>::get(class) {
+			Some(collection) => collection.transferable && !Self::is_token_frozen(*class, *instance),
+			None => false,
+		}
+	}
+}
+
+impl<T: Config> InspectEnumerable<T::AccountId> for Pallet<T> {
+	fn classes() -> Box<dyn Iterator<Item = Self::ClassId>> {
+		Box::new(Collections::<T>::iter_keys())
+	}
+
+	fn instances(class: &Self::ClassId) -> Box<dyn Iterator<Item = Self::InstanceId>> {
+		Box::new(Tokens::<T>::iter_key_prefix(class))
+	}
+
+	fn owned(who: &T::AccountId) -> Box<dyn Iterator<Item = (Self::ClassId, Self::InstanceId)>> {
+		Box::new(OwnedTokens::<T>::iter_key_prefix((who,)))
+	}
+
+	fn owned_in_class(class: &Self::ClassId, who: &T::AccountId) -> Box<dyn Iterator<Item = Self::InstanceId>> {
+		Box::new(OwnedTokens::<T>::iter_key_prefix((who, class)))
+	}
+}
+
+impl<T: Config> Create<T::AccountId> for Pallet<T> {
+	/// Create a transferable collection without a deposit or supply limit, owned by `who`, that
+	/// `admin` can mint in.
+	fn create_class(class: &Self::ClassId, who: &T::AccountId, admin: &T::AccountId) -> DispatchResult {
+		ensure!(!Collections::<T>::contains_key(class), Error::<T>::CollectionExists);
+
+		Self::do_create_collection(*class, Some(who.clone()), Some(admin.clone()), Zero::zero(), u32::MAX, Vec::new(), true, true)
+	}
+}
+
+impl<T: Config> Mutate<T::AccountId> for Pallet<T> {
+	/// Token ids are assigned in order, so `instance` must be the collection's next id.
+	fn mint_into(class: &Self::ClassId, instance: &Self::InstanceId, who: &T::AccountId) -> DispatchResult {
+		let collection = Collections::<T>::get(class).ok_or(Error::<T>::CollectionDoesNotExist)?;
+		let next = Self::get_total_tokens(class).checked_add(1).ok_or(ArithmeticError::Overflow)?;
+		ensure!(*instance == next, Error::<T>::InvalidTokenId);
+
+		Self::do_mint(*class, collection, who.clone()).map(|_| ())
+	}
+
+	fn burn_from(class: &Self::ClassId, instance: &Self::InstanceId) -> DispatchResult {
+		let token = Tokens::<T>::get(class, instance).ok_or(Error::<T>::TokenDoesNotExist)?;
+
+		Self::do_burn(*class, token)
+	}
+
+	fn set_attribute(class: &Self::ClassId, instance: &Self::InstanceId, key: &[u8], value: &[u8]) -> DispatchResult {
+		Self::do_set_attribute(*class, Some(*instance), key.to_vec(), value.to_vec(), false)
+	}
+
+	fn set_class_attribute(class: &Self::ClassId, key: &[u8], value: &[u8]) -> DispatchResult {
+		Self::do_set_attribute(*class, None, key.to_vec(), value.to_vec(), false)
+	}
+}
+
+impl<T: Config> Transfer<T::AccountId> for Pallet<T> {
+	fn transfer(class: &Self::ClassId, instance: &Self::InstanceId, destination: &T::AccountId) -> DispatchResult {
+		Self::do_transfer(*class, *instance, None, destination.clone())
+	}
+}
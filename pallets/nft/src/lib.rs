@@ -2,6 +2,10 @@
 
 pub use pallet::*;
 
+pub mod migrations;
+
+mod impl_nonfungibles;
+
 #[cfg(test)]
 mod mock;
 
@@ -10,11 +14,11 @@ mod tests;
 
 #[frame_support::pallet]
 pub mod pallet {
-	use frame_support::pallet_prelude::*;
+	use frame_support::{pallet_prelude::*, traits::{Currency, ReservableCurrency}, transactional};
     use frame_system::pallet_prelude::*;
 	use scale_info::TypeInfo;
 
-	use sp_runtime::ArithmeticError;
+	use sp_runtime::{traits::Zero, ArithmeticError};
 	use sp_std::vec::Vec;
 
 	#[cfg(feature = "std")]
@@ -23,10 +27,42 @@ pub mod pallet {
     #[pallet::config]
 	pub trait Config: frame_system::Config {
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+		type Currency: ReservableCurrency<Self::AccountId>;
+
+		/// Amount reserved from an account that creates a collection.
+		#[pallet::constant]
+		type CollectionDeposit: Get<BalanceOf<Self>>;
+
+		/// Maximum length of a token's or a collection's metadata.
+		#[pallet::constant]
+		type MetadataLimit: Get<u32>;
+
+		/// Maximum length of an attribute key.
+		#[pallet::constant]
+		type KeyLimit: Get<u32>;
+
+		/// Maximum length of an attribute value.
+		#[pallet::constant]
+		type ValueLimit: Get<u32>;
+
+		/// Maximum number of attributes on a collection, or on any one of its tokens.
+		#[pallet::constant]
+		type MaxAttributes: Get<u32>;
+
+		/// Collection ids below this are left for root to create with `force_create_collection`.
+		/// Signed creators are given the next free id from it up.
+		#[pallet::constant]
+		type ReservedCollections: Get<u32>;
 	}
 
+	pub type BalanceOf<T> = <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+	/// The current storage version.
+	pub(crate) const STORAGE_VERSION: StorageVersion = StorageVersion::new(4);
+
     #[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	#[pallet::without_storage_info]
 	pub struct Pallet<T>(_);
 
@@ -37,6 +73,16 @@ pub mod pallet {
 		pub total_supply: u32,
 		pub created_at: T::BlockNumber,
 		pub metadata: Vec<u8>,
+		/// Whether owners can transfer their tokens. Tokens of other collections are soulbound.
+		pub transferable: bool,
+		/// Whether an account can hold more than one token of the collection.
+		pub allow_multiple_per_account: bool,
+		/// The account that created the collection, or `None` if root created it without one.
+		pub owner: Option<T::AccountId>,
+		/// The account allowed to mint in the collection besides root.
+		pub issuer: Option<T::AccountId>,
+		/// Amount reserved from the owner.
+		pub deposit: BalanceOf<T>,
 	}
 
 	#[derive(Clone, Encode, Decode, PartialEq, Debug, TypeInfo, Eq)]
@@ -52,6 +98,19 @@ pub mod pallet {
 		CollectionCreated(u32),
 		NFTMinted(u32, u32, T::AccountId),
 		NFTBurned(u32, u32, T::AccountId),		
+		CollectionSupplyChanged(u32, u32),
+		Transferred(u32, u32, T::AccountId, T::AccountId),
+		IssuerChanged(u32, Option<T::AccountId>),
+		TokenMetadataSet(u32, u32),
+		TokenMetadataCleared(u32, u32),
+		AttributeSet(u32, Option<u32>, Vec<u8>, Vec<u8>),
+		AttributeCleared(u32, Option<u32>, Vec<u8>),
+		TokenFrozen(u32, u32),
+		TokenThawed(u32, u32),
+		CollectionFrozen(u32),
+		CollectionThawed(u32),
+		CollectionMetadataSet(u32),
+		CollectionDestroyed(u32),
 	}
 
 	// Errors inform users that something went wrong.
@@ -65,12 +124,30 @@ pub mod pallet {
 		TokenMaxSupplyReached,
 		TokenDoesNotExist,
 		NullValue,
+		SupplyBelowActiveTokens,
+		Soulbound,
+		NotTheIssuer,
+		NotTheCollectionOwner,
+		MetadataTooLong,
+		KeyTooLong,
+		ValueTooLong,
+		AttributeLocked,
+		Frozen,
+		InvalidTokenId,
+		CollectionNotEmpty,
+		TooManyAttributes,
+		BadWitness,
 	}
 
 	#[pallet::storage]
 	#[pallet::getter(fn get_total_collections)]
 	pub(super) type TotalCollections<T> = StorageValue<_, u32,ValueQuery>;
 
+	/// Lowest id a signed creator may be given next.
+	#[pallet::storage]
+	#[pallet::getter(fn get_next_collection_id)]
+	pub(super) type NextCollectionId<T> = StorageValue<_, u32,ValueQuery>;
+
 	#[pallet::storage]
 	#[pallet::getter(fn get_collection)]
 	pub(super) type Collections<T:Config> = StorageMap<
@@ -101,51 +178,381 @@ pub mod pallet {
 		ValueQuery,
 	>;
 
+	/// Tokens by collection and token id.
 	#[pallet::storage]
 	#[pallet::getter(fn get_token)]
-	pub(super) type Tokens<T:Config> = StorageMap<
+	pub(super) type Tokens<T:Config> = StorageDoubleMap<
 		_,
 		Blake2_128Concat,
-		(T::AccountId,u32),
+		u32,
+		Blake2_128Concat,
+		u32,
 		Token<T>,
 		OptionQuery,
 	>;
 
+	/// Index of the tokens an account holds, by account, collection and token id.
+	#[pallet::storage]
+	pub(super) type OwnedTokens<T:Config> = StorageNMap<
+		_,
+		(
+			NMapKey<Blake2_128Concat, T::AccountId>,
+			NMapKey<Blake2_128Concat, u32>,
+			NMapKey<Blake2_128Concat, u32>,
+		),
+		(),
+		OptionQuery,
+	>;
+
+	/// Metadata of a token, by collection and token id.
+	#[pallet::storage]
+	#[pallet::getter(fn get_token_metadata)]
+	pub(super) type TokenMetadata<T:Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		u32,
+		Blake2_128Concat,
+		u32,
+		Vec<u8>,
+		OptionQuery,
+	>;
+
+	/// Attributes of a collection, or of one of its tokens, with whether they are locked.
+	#[pallet::storage]
+	#[pallet::getter(fn get_attribute)]
+	pub(super) type Attributes<T:Config> = StorageNMap<
+		_,
+		(
+			NMapKey<Blake2_128Concat, u32>,
+			NMapKey<Blake2_128Concat, Option<u32>>,
+			NMapKey<Blake2_128Concat, Vec<u8>>,
+		),
+		(Vec<u8>, bool),
+		OptionQuery,
+	>;
+
+	/// Number of attributes set on a collection, or on one of its tokens.
+	#[pallet::storage]
+	#[pallet::getter(fn get_attribute_count)]
+	pub(super) type AttributeCounts<T:Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		u32,
+		Blake2_128Concat,
+		Option<u32>,
+		u32,
+		ValueQuery,
+	>;
+
+	/// Frozen tokens, by collection and token id.
+	#[pallet::storage]
+	pub(super) type FrozenTokens<T:Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		u32,
+		Blake2_128Concat,
+		u32,
+		(),
+		OptionQuery,
+	>;
+
+	/// Collections whose tokens are all frozen.
+	#[pallet::storage]
+	pub(super) type FrozenCollections<T:Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		u32,
+		(),
+		OptionQuery,
+	>;
+
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 
+		/// Create a collection owned and issued by the caller, reserving `CollectionDeposit`. It is
+		/// given the next free id from `ReservedCollections` up.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(5,5))]
+		#[transactional]
+		pub fn create_collection(origin: OriginFor<T>, total_supply: u32, metadata: Vec<u8>, transferable: bool,
+		allow_multiple_per_account: bool) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let uid = Self::next_collection_id()?;
+			let deposit = T::CollectionDeposit::get();
+			T::Currency::reserve(&who,deposit)?;
+
+			Self::do_create_collection(uid,Some(who.clone()),Some(who),deposit,total_supply,metadata,transferable,allow_multiple_per_account)
+		}
+
+		/// Create a collection without a deposit. Only root can mint in it until the owner, if
+		/// any, sets an issuer.
 		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3,3))]
-		pub fn create_collection(origin: OriginFor<T>,uid: u32, total_supply: u32, metadata: Vec<u8>) -> DispatchResult {
-			ensure_root(origin)?;// Temporary
-			//let who = ensure_signed(origin)?;
+		pub fn force_create_collection(origin: OriginFor<T>,uid: u32, owner: Option<T::AccountId>, total_supply: u32, metadata: Vec<u8>,
+		transferable: bool, allow_multiple_per_account: bool) -> DispatchResult {
+			ensure_root(origin)?;
 			ensure!(!Collections::<T>::contains_key(uid.clone()),Error::<T>::CollectionExists);
-			let now = <frame_system::Pallet<T>>::block_number();
 
-			let collection = Collection::<T> {
-				total_supply: total_supply,
-				created_at: now,
-				metadata: metadata,
-			};
+			Self::do_create_collection(uid,owner,None,Zero::zero(),total_supply,metadata,transferable,allow_multiple_per_account)
+		}
 
-			Collections::<T>::insert(uid.clone(),&collection);
-			let total = Self::get_total_collections().checked_add(1).ok_or(ArithmeticError::Overflow)?;
-			TotalCollections::<T>::put(total);
+		/// Let `issuer` mint in the caller's collection, or only root if `None`.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1))]
+		pub fn set_issuer(origin: OriginFor<T>, collection_id: u32, issuer: Option<T::AccountId>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let mut collection = Self::get_collection(collection_id.clone()).ok_or(Error::<T>::CollectionDoesNotExist)?;
+			ensure!(collection.owner.as_ref() == Some(&who),Error::<T>::NotTheCollectionOwner);
+
+			collection.issuer = issuer.clone();
+			Collections::<T>::insert(collection_id.clone(),&collection);
+
+			Self::deposit_event(Event::IssuerChanged(collection_id,issuer));
 
-			Self::deposit_event(Event::CollectionCreated(uid));
-			
 			Ok(())
 		}
 
-		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(5,3))]
+		/// Mint a token to `who`. Root can mint in any collection, and the issuer in its own.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(5,4))]
 		pub fn mint(origin: OriginFor<T>, collection_id: u32, who: T::AccountId) -> DispatchResult {
-			ensure_root(origin)?;
-			ensure!(Collections::<T>::contains_key(collection_id.clone()),Error::<T>::CollectionDoesNotExist);
+			let issuer = ensure_signed(origin.clone()).ok();
+			if issuer.is_none() {
+				ensure_root(origin)?;
+			}
+			let collection = Self::get_collection(collection_id.clone()).ok_or(Error::<T>::CollectionDoesNotExist)?;
+			if let Some(issuer) = issuer {
+				ensure!(collection.issuer == Some(issuer),Error::<T>::NotTheIssuer);
+			}
+
+			Self::do_mint(collection_id,collection,who).map(|_| ())
+		}
+
+		/// Move one of the caller's tokens in a transferable collection to `dest`. Unless the
+		/// collection allows several tokens per account, `dest` must not hold one already.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(4,3))]
+		pub fn transfer(origin: OriginFor<T>, collection_id: u32, token_id: u32, dest: T::AccountId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			Self::do_transfer(collection_id,token_id,Some(who),dest)
+		}
+
+		/// Change the number of tokens a collection can hold. It cannot go below the tokens
+		/// already minted and not burned. Root or the collection owner can call this.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2,1))]
+		pub fn set_total_supply(origin: OriginFor<T>, collection_id: u32, total_supply: u32) -> DispatchResult {
+			Self::ensure_collection_owner(origin,collection_id.clone())?;
+			let mut collection = Self::get_collection(collection_id.clone()).ok_or(Error::<T>::CollectionDoesNotExist)?;
+			ensure!(total_supply >= Self::get_active_tokens(collection_id.clone()),Error::<T>::SupplyBelowActiveTokens);
+
+			collection.total_supply = total_supply;
+			Collections::<T>::insert(collection_id.clone(),&collection);
+
+			Self::deposit_event(Event::CollectionSupplyChanged(collection_id,total_supply));
+
+			Ok(())
+		}
+
+		/// Replace the metadata of a collection. Root or the collection owner can call this.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1))]
+		pub fn set_collection_metadata(origin: OriginFor<T>, collection_id: u32, metadata: Vec<u8>) -> DispatchResult {
+			Self::ensure_collection_owner(origin,collection_id.clone())?;
+			ensure!(metadata.len() as u32 <= T::MetadataLimit::get(),Error::<T>::MetadataTooLong);
+			let mut collection = Self::get_collection(collection_id.clone()).ok_or(Error::<T>::CollectionDoesNotExist)?;
+
+			collection.metadata = metadata;
+			Collections::<T>::insert(collection_id.clone(),&collection);
+
+			Self::deposit_event(Event::CollectionMetadataSet(collection_id));
+
+			Ok(())
+		}
+
+		/// Remove a collection with no tokens left, with its attributes, and return the deposit to
+		/// its owner. `attributes` must be at least the number of attributes set on the collection
+		/// itself, and the call is weighed by it. Root or the collection owner can call this.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(5,7) + T::DbWeight::get().writes(*attributes as u64))]
+		pub fn destroy_collection(origin: OriginFor<T>, collection_id: u32, attributes: u32) -> DispatchResult {
+			Self::ensure_collection_owner(origin,collection_id.clone())?;
+			let collection = Self::get_collection(collection_id.clone()).ok_or(Error::<T>::CollectionDoesNotExist)?;
+			ensure!(Self::get_active_tokens(collection_id.clone()) == 0,Error::<T>::CollectionNotEmpty);
+			ensure!(Self::get_attribute_count(collection_id.clone(),None::<u32>) <= attributes,Error::<T>::BadWitness);
+
+			if let Some(owner) = collection.owner {
+				T::Currency::unreserve(&owner,collection.deposit);
+			}
+			Collections::<T>::remove(collection_id.clone());
+			TotalTokens::<T>::remove(collection_id.clone());
+			ActiveTokens::<T>::remove(collection_id.clone());
+			// Burning a token clears its metadata, attributes and frozen flag, so only the
+			// collection's own attributes are left
+			Attributes::<T>::remove_prefix((collection_id.clone(),None::<u32>),Some(attributes));
+			AttributeCounts::<T>::remove(collection_id.clone(),None::<u32>);
+			FrozenCollections::<T>::remove(collection_id.clone());
+			TotalCollections::<T>::put(Self::get_total_collections().saturating_sub(1));
+
+			Self::deposit_event(Event::CollectionDestroyed(collection_id));
+
+			Ok(())
+		}
+
+		/// Set the metadata of a token. Root or the collection owner can call this.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2,1))]
+		pub fn set_token_metadata(origin: OriginFor<T>, collection_id: u32, token_id: u32, data: Vec<u8>) -> DispatchResult {
+			Self::ensure_collection_owner(origin,collection_id.clone())?;
+			Self::ensure_token_minted(collection_id.clone(),token_id.clone())?;
+			ensure!(data.len() as u32 <= T::MetadataLimit::get(),Error::<T>::MetadataTooLong);
+
+			TokenMetadata::<T>::insert(collection_id.clone(),token_id.clone(),data);
+
+			Self::deposit_event(Event::TokenMetadataSet(collection_id,token_id));
+
+			Ok(())
+		}
+
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1))]
+		pub fn clear_token_metadata(origin: OriginFor<T>, collection_id: u32, token_id: u32) -> DispatchResult {
+			Self::ensure_collection_owner(origin,collection_id.clone())?;
+
+			TokenMetadata::<T>::remove(collection_id.clone(),token_id.clone());
+
+			Self::deposit_event(Event::TokenMetadataCleared(collection_id,token_id));
+
+			Ok(())
+		}
+
+		/// Set an attribute of a collection, or of one of its tokens when `token_id` is given.
+		/// Each can hold up to `MaxAttributes` attributes. A locked attribute can no longer be
+		/// changed or cleared. Root or the collection owner can call this.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(4,2))]
+		pub fn set_attribute(origin: OriginFor<T>, collection_id: u32, token_id: Option<u32>, key: Vec<u8>,
+		value: Vec<u8>, lock: bool) -> DispatchResult {
+			Self::ensure_collection_owner(origin,collection_id.clone())?;
+
+			Self::do_set_attribute(collection_id,token_id,key,value,lock)
+		}
+
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2,2))]
+		pub fn clear_attribute(origin: OriginFor<T>, collection_id: u32, token_id: Option<u32>, key: Vec<u8>) -> DispatchResult {
+			Self::ensure_collection_owner(origin,collection_id.clone())?;
+			let attribute_key = (collection_id.clone(),token_id.clone(),key.clone());
+			ensure!(!Self::is_attribute_locked(attribute_key.clone()),Error::<T>::AttributeLocked);
+
+			if Attributes::<T>::take(attribute_key).is_some() {
+				AttributeCounts::<T>::mutate(collection_id.clone(),token_id.clone(),|count| *count = count.saturating_sub(1));
+			}
+
+			Self::deposit_event(Event::AttributeCleared(collection_id,token_id,key));
+
+			Ok(())
+		}
+
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(4,7) + T::DbWeight::get().writes(T::MaxAttributes::get() as u64))]
+		pub fn burn(origin: OriginFor<T>,collection_id: u32, token_id: u32) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let token = Self::get_token(collection_id.clone(),token_id.clone()).ok_or(Error::<T>::TokenDoesNotExist)?;
+			ensure!(token.owner == who,Error::<T>::NotTheOwner);
+			// A suspended holder cannot get rid of the evidence
+			ensure!(!Self::is_token_frozen(collection_id.clone(),token_id.clone()),Error::<T>::Frozen);
+			
+			Self::do_burn(collection_id,token)
+		}
+
+		/// Burn a token whoever holds it, frozen or not, for example to revoke a badge. Root or the
+		/// collection owner can call this. It takes the token id rather than the holder, as a
+		/// collection can allow several tokens per account; `token_of` finds a holder's token.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3,7) + T::DbWeight::get().writes(T::MaxAttributes::get() as u64))]
+		pub fn force_burn(origin: OriginFor<T>, collection_id: u32, token_id: u32) -> DispatchResult {
+			Self::ensure_collection_owner(origin,collection_id.clone())?;
+			let token = Self::get_token(collection_id.clone(),token_id.clone()).ok_or(Error::<T>::TokenDoesNotExist)?;
+
+			Self::do_burn(collection_id,token)
+		}
+
+		/// Suspend a token: it cannot be transferred and `is_frozen` reports it. Root or the
+		/// collection owner can call this.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2,1))]
+		pub fn freeze(origin: OriginFor<T>, collection_id: u32, token_id: u32) -> DispatchResult {
+			Self::ensure_collection_owner(origin,collection_id.clone())?;
+			Self::ensure_token_minted(collection_id.clone(),token_id.clone())?;
+
+			FrozenTokens::<T>::insert(collection_id.clone(),token_id.clone(),());
+
+			Self::deposit_event(Event::TokenFrozen(collection_id,token_id));
+
+			Ok(())
+		}
+
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1))]
+		pub fn thaw(origin: OriginFor<T>, collection_id: u32, token_id: u32) -> DispatchResult {
+			Self::ensure_collection_owner(origin,collection_id.clone())?;
+
+			FrozenTokens::<T>::remove(collection_id.clone(),token_id.clone());
+
+			Self::deposit_event(Event::TokenThawed(collection_id,token_id));
+
+			Ok(())
+		}
+
+		/// Suspend every token of a collection.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1))]
+		pub fn freeze_collection(origin: OriginFor<T>, collection_id: u32) -> DispatchResult {
+			Self::ensure_collection_owner(origin,collection_id.clone())?;
+
+			FrozenCollections::<T>::insert(collection_id.clone(),());
+
+			Self::deposit_event(Event::CollectionFrozen(collection_id));
+
+			Ok(())
+		}
+
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1))]
+		pub fn thaw_collection(origin: OriginFor<T>, collection_id: u32) -> DispatchResult {
+			Self::ensure_collection_owner(origin,collection_id.clone())?;
+
+			FrozenCollections::<T>::remove(collection_id.clone());
+
+			Self::deposit_event(Event::CollectionThawed(collection_id));
+
+			Ok(())
+		}
+
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Whether a token `who` holds in a collection is frozen, on its own or with the whole
+		/// collection. Accounts without a token there are not frozen.
+		pub fn is_frozen(collection_id: u32, who: &T::AccountId) -> bool {
+			Self::tokens_of(collection_id.clone(),who).into_iter().any(|token_id| Self::is_token_frozen(collection_id,token_id))
+		}
+
+		/// Ids of the tokens `who` holds in a collection.
+		pub fn tokens_of(collection_id: u32, who: &T::AccountId) -> Vec<u32> {
+			OwnedTokens::<T>::iter_key_prefix((who.clone(),collection_id)).collect()
+		}
+
+		/// Id of a token `who` holds in a collection, for collections with one token per account.
+		pub fn token_of(collection_id: u32, who: &T::AccountId) -> Option<u32> {
+			OwnedTokens::<T>::iter_key_prefix((who.clone(),collection_id)).next()
+		}
+
+		/// Accounts holding a token of a collection, with the id of each token.
+		pub fn holders(collection_id: u32) -> Vec<(u32, T::AccountId)> {
+			Tokens::<T>::iter_prefix(collection_id).map(|(token_id, token)| (token_id, token.owner)).collect()
+		}
+
+		/// Whether `who` holds a token of a collection that is not frozen.
+		pub fn holds_unfrozen_token(collection_id: u32, who: &T::AccountId) -> bool {
+			Self::tokens_of(collection_id.clone(),who).into_iter().any(|token_id| !Self::is_token_frozen(collection_id,token_id))
+		}
+
+		pub(crate) fn is_token_frozen(collection_id: u32, token_id: u32) -> bool {
+			FrozenCollections::<T>::contains_key(collection_id) || FrozenTokens::<T>::contains_key(collection_id,token_id)
+		}
+
+		/// Mint the next token of a collection to `who`, returning its id.
+		pub(crate) fn do_mint(collection_id: u32, collection: Collection<T>, who: T::AccountId) -> Result<u32, DispatchError> {
 			// Active Tokens <= total_supply
 			let mut active = Self::get_active_tokens(collection_id);
-			let collection = Self::get_collection(collection_id.clone()).unwrap();
 			ensure!(active < collection.total_supply,Error::<T>::TokenMaxSupplyReached);
 			// Ensure one Token per user policy
-			ensure!(!Tokens::<T>::contains_key((who.clone(),collection_id.clone())),Error::<T>::OneAccountOneToken);
+			ensure!(collection.allow_multiple_per_account || Self::token_of(collection_id.clone(),&who).is_none(),Error::<T>::OneAccountOneToken);
 			//uid from total tokens
 			let uid = Self::get_total_tokens(collection_id.clone()).checked_add(1).ok_or(ArithmeticError::Overflow)?;
 
@@ -154,36 +561,197 @@ pub mod pallet {
 				owner: who.clone(),
 			};
 
-			Tokens::<T>::insert((who.clone(),collection_id.clone()),token);
+			Tokens::<T>::insert(collection_id.clone(),uid.clone(),token);
+			OwnedTokens::<T>::insert((who.clone(),collection_id.clone(),uid.clone()),());
 			active = active + 1;
 			ActiveTokens::<T>::insert(collection_id.clone(),active);
 			TotalTokens::<T>::insert(collection_id.clone(),uid.clone());
 
-			Self::deposit_event(Event::NFTMinted(collection_id,uid,who));
+			Self::deposit_event(Event::NFTMinted(collection_id,uid.clone(),who));
+
+			Ok(uid)
+		}
+
+		/// Move a token to `dest`, checking that it belongs to `who` if given.
+		pub(crate) fn do_transfer(collection_id: u32, token_id: u32, who: Option<T::AccountId>, dest: T::AccountId) -> DispatchResult {
+			let collection = Self::get_collection(collection_id.clone()).ok_or(Error::<T>::CollectionDoesNotExist)?;
+			ensure!(collection.transferable,Error::<T>::Soulbound);
+			let mut token = Self::get_token(collection_id.clone(),token_id.clone()).ok_or(Error::<T>::TokenDoesNotExist)?;
+			if let Some(who) = who {
+				ensure!(token.owner == who,Error::<T>::NotTheOwner);
+			}
+			ensure!(!Self::is_token_frozen(collection_id.clone(),token_id.clone()),Error::<T>::Frozen);
+			ensure!(collection.allow_multiple_per_account || Self::token_of(collection_id.clone(),&dest).is_none(),Error::<T>::OneAccountOneToken);
+
+			let who = token.owner.clone();
+			token.owner = dest.clone();
+			Tokens::<T>::insert(collection_id.clone(),token_id.clone(),token);
+			OwnedTokens::<T>::remove((who.clone(),collection_id.clone(),token_id.clone()));
+			OwnedTokens::<T>::insert((dest.clone(),collection_id.clone(),token_id.clone()),());
+
+			Self::deposit_event(Event::Transferred(collection_id,token_id,who,dest));
 
 			Ok(())
 		}
 
-		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2,2))]
-		pub fn burn(origin: OriginFor<T>,collection_id: u32) -> DispatchResult {
-			let who = ensure_signed(origin)?;
-			
-			ensure!(Tokens::<T>::contains_key((who.clone(),collection_id.clone())),Error::<T>::TokenDoesNotExist);
-			let token = Self::get_token((who.clone(),collection_id.clone())).ok_or(Error::<T>::NullValue)?;
-			
+		pub(crate) fn do_burn(collection_id: u32, token: Token<T>) -> DispatchResult {
 			let uid = token.id;
 			
 			let mut active = Self::get_active_tokens(collection_id);
 
-			Tokens::<T>::remove((who.clone(),collection_id.clone()));
+			Tokens::<T>::remove(collection_id.clone(),uid.clone());
+			OwnedTokens::<T>::remove((token.owner.clone(),collection_id.clone(),uid.clone()));
+			FrozenTokens::<T>::remove(collection_id.clone(),uid.clone());
+			TokenMetadata::<T>::remove(collection_id.clone(),uid.clone());
+			// A token holds at most `MaxAttributes` attributes
+			Attributes::<T>::remove_prefix((collection_id.clone(),Some(uid.clone())),Some(T::MaxAttributes::get()));
+			AttributeCounts::<T>::remove(collection_id.clone(),Some(uid.clone()));
 			active = active - 1;
 			ActiveTokens::<T>::insert(collection_id.clone(),active);
 
-			Self::deposit_event(Event::NFTBurned(collection_id,uid,who));
+			Self::deposit_event(Event::NFTBurned(collection_id,uid,token.owner));
+			
+			Ok(())
+		}
+
+		pub(crate) fn do_set_attribute(collection_id: u32, token_id: Option<u32>, key: Vec<u8>, value: Vec<u8>, lock: bool) -> DispatchResult {
+			if let Some(token_id) = token_id {
+				Self::ensure_token_minted(collection_id.clone(),token_id)?;
+			}
+			ensure!(key.len() as u32 <= T::KeyLimit::get(),Error::<T>::KeyTooLong);
+			ensure!(value.len() as u32 <= T::ValueLimit::get(),Error::<T>::ValueTooLong);
+			let attribute_key = (collection_id.clone(),token_id.clone(),key.clone());
+			match Attributes::<T>::get(attribute_key.clone()) {
+				Some((_, locked)) => ensure!(!locked,Error::<T>::AttributeLocked),
+				None => AttributeCounts::<T>::try_mutate(collection_id.clone(),token_id.clone(),|count| -> DispatchResult {
+					ensure!(*count < T::MaxAttributes::get(),Error::<T>::TooManyAttributes);
+					*count += 1;
+					Ok(())
+				})?,
+			}
+
+			Attributes::<T>::insert(attribute_key,(value.clone(),lock));
+
+			Self::deposit_event(Event::AttributeSet(collection_id,token_id,key,value));
+
+			Ok(())
+		}
+
+		/// Ensure the origin is root or the owner of the collection.
+		fn ensure_collection_owner(origin: OriginFor<T>, collection_id: u32) -> DispatchResult {
+			let who = ensure_signed(origin.clone()).ok();
+			if who.is_none() {
+				ensure_root(origin)?;
+			}
+			let collection = Self::get_collection(collection_id).ok_or(Error::<T>::CollectionDoesNotExist)?;
+			if let Some(who) = who {
+				ensure!(collection.owner == Some(who),Error::<T>::NotTheCollectionOwner);
+			}
+			Ok(())
+		}
+
+		/// Ensure a token with this id has been minted in the collection and not burned since.
+		fn ensure_token_minted(collection_id: u32, token_id: u32) -> DispatchResult {
+			ensure!(Tokens::<T>::contains_key(collection_id,token_id),Error::<T>::TokenDoesNotExist);
+			Ok(())
+		}
+
+		fn is_attribute_locked(key: (u32, Option<u32>, Vec<u8>)) -> bool {
+			Attributes::<T>::get(key).map_or(false, |(_, locked)| locked)
+		}
+
+		/// Take the next free collection id for a signed creator. Ids root has already taken with
+		/// `force_create_collection` are skipped.
+		fn next_collection_id() -> Result<u32, DispatchError> {
+			let mut uid = Self::get_next_collection_id().max(T::ReservedCollections::get());
+			while Collections::<T>::contains_key(uid) {
+				uid = uid.checked_add(1).ok_or(ArithmeticError::Overflow)?;
+			}
+			NextCollectionId::<T>::put(uid.checked_add(1).ok_or(ArithmeticError::Overflow)?);
+			Ok(uid)
+		}
+
+		pub(crate) fn do_create_collection(uid: u32, owner: Option<T::AccountId>, issuer: Option<T::AccountId>, deposit: BalanceOf<T>,
+		total_supply: u32, metadata: Vec<u8>, transferable: bool, allow_multiple_per_account: bool) -> DispatchResult {
+			ensure!(metadata.len() as u32 <= T::MetadataLimit::get(),Error::<T>::MetadataTooLong);
+			let total = Self::get_total_collections().checked_add(1).ok_or(ArithmeticError::Overflow)?;
+			let now = <frame_system::Pallet<T>>::block_number();
+
+			let collection = Collection::<T> {
+				total_supply: total_supply,
+				created_at: now,
+				metadata: metadata,
+				transferable: transferable,
+				allow_multiple_per_account: allow_multiple_per_account,
+				owner: owner,
+				issuer: issuer,
+				deposit: deposit,
+			};
+
+			Collections::<T>::insert(uid.clone(),&collection);
+			TotalCollections::<T>::put(total);
+
+			Self::deposit_event(Event::CollectionCreated(uid));
 			
 			Ok(())
 		}
 
+		/// Check that every collection's `ActiveTokens` matches its `Tokens` entries and stays
+		/// within the collection's supply, and that `OwnedTokens` indexes exactly those tokens.
+		/// Polkadot v0.9.18 has no `try_state` hook, so this only runs from the migration's
+		/// `post_upgrade` under `try-runtime` and from the tests.
+		#[cfg(any(feature = "try-runtime", test))]
+		pub fn do_try_state() -> Result<(), &'static str> {
+			let mut held = sp_std::collections::btree_map::BTreeMap::<u32, u32>::new();
+			for (collection_id, token_id, token) in Tokens::<T>::iter() {
+				let collection = Self::get_collection(collection_id).ok_or("token in a collection that does not exist")?;
+				ensure!(token.id == token_id,"token id does not match its key");
+				ensure!(
+					OwnedTokens::<T>::contains_key((token.owner.clone(),collection_id,token_id)),
+					"token is missing from the owner index"
+				);
+				ensure!(
+					collection.allow_multiple_per_account || Self::tokens_of(collection_id,&token.owner).len() == 1,
+					"account holds several tokens of a collection that allows one"
+				);
+				*held.entry(collection_id).or_default() += 1;
+			}
+			ensure!(
+				OwnedTokens::<T>::iter_keys().count() as u32 == held.values().sum::<u32>(),
+				"owner index has entries without a token"
+			);
+			for (collection_id, collection) in Collections::<T>::iter() {
+				let active = Self::get_active_tokens(collection_id);
+				ensure!(active == held.get(&collection_id).copied().unwrap_or_default(),"active tokens do not match the tokens held");
+				ensure!(active <= collection.total_supply,"active tokens exceed the collection supply");
+			}
+			ensure!(
+				TokenMetadata::<T>::iter_keys().all(|(collection_id, token_id)| Tokens::<T>::contains_key(collection_id,token_id)),
+				"metadata left for a token that does not exist"
+			);
+			ensure!(
+				FrozenTokens::<T>::iter_keys().all(|(collection_id, token_id)| Tokens::<T>::contains_key(collection_id,token_id)),
+				"frozen entry left for a token that does not exist"
+			);
+			ensure!(
+				Attributes::<T>::iter_keys().all(|(collection_id, token_id, _)| match token_id {
+					Some(token_id) => Tokens::<T>::contains_key(collection_id,token_id),
+					None => true,
+				}),
+				"attribute left for a token that does not exist"
+			);
+			let mut attributes = sp_std::collections::btree_map::BTreeMap::<(u32, Option<u32>), u32>::new();
+			for (collection_id, token_id, _) in Attributes::<T>::iter_keys() {
+				*attributes.entry((collection_id, token_id)).or_default() += 1;
+			}
+			for (collection_id, token_id, count) in AttributeCounts::<T>::iter() {
+				ensure!(count <= T::MaxAttributes::get(),"more attributes than MaxAttributes");
+				ensure!(attributes.remove(&(collection_id, token_id)).unwrap_or_default() == count,"attribute count does not match the attributes set");
+			}
+			ensure!(attributes.is_empty(),"attributes set without a count");
+
+			Ok(())
+		}
 	}
 
 }
\ No newline at end of file
@@ -0,0 +1,219 @@
+//! Storage migrations for the nft pallet, one `vN` module per version. The runtime runs them
+//! through `Migration`.
+
+use crate::pallet::Config;
+use frame_support::{pallet_prelude::*, traits::OnRuntimeUpgrade};
+#[cfg(feature = "try-runtime")]
+use crate::pallet::{Collections, Pallet, Token, Tokens, STORAGE_VERSION};
+#[cfg(feature = "try-runtime")]
+use frame_support::{
+	storage::migration::storage_iter,
+	traits::{GetStorageVersion, OnRuntimeUpgradeHelpersExt, PalletInfoAccess},
+};
+use sp_std::marker::PhantomData;
+
+/// Brings nft storage up to `STORAGE_VERSION` from any earlier version.
+pub struct Migration<T>(PhantomData<T>);
+
+impl<T: Config> OnRuntimeUpgrade for Migration<T> {
+	fn on_runtime_upgrade() -> Weight {
+		v1::migrate::<T>() + v2::migrate::<T>() + v3::migrate::<T>() + v4::migrate::<T>()
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<(), &'static str> {
+		Self::set_temp_storage(Collections::<T>::iter_keys().count() as u64, "collections");
+		// Token keys change layout, so count the raw entries
+		let tokens = storage_iter::<Token<T>>(Pallet::<T>::name().as_bytes(), b"Tokens").count();
+		Self::set_temp_storage(tokens as u64, "tokens");
+		Ok(())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade() -> Result<(), &'static str> {
+		ensure!(Pallet::<T>::on_chain_storage_version() == STORAGE_VERSION, "nft storage version was not updated");
+		// Entries that fail to decode are skipped by `iter`, so a layout mismatch shows up here
+		let collections: u64 = Self::get_temp_storage("collections").ok_or("collection count was not recorded")?;
+		ensure!(Collections::<T>::iter().count() as u64 == collections, "some collections do not decode");
+		let tokens: u64 = Self::get_temp_storage("tokens").ok_or("token count was not recorded")?;
+		ensure!(Tokens::<T>::iter().count() as u64 == tokens, "some tokens do not decode");
+		Pallet::<T>::do_try_state()
+	}
+}
+
+pub mod v1 {
+	use crate::pallet::{Config, Pallet};
+	use frame_support::{
+		pallet_prelude::*,
+		traits::{GetStorageVersion, StorageVersion},
+	};
+
+	/// Record the first storage version. `Collection` and `Token` keep their layout.
+	pub fn migrate<T: Config>() -> Weight {
+		let on_chain = Pallet::<T>::on_chain_storage_version();
+		if on_chain >= 1 {
+			return T::DbWeight::get().reads(1);
+		}
+
+		StorageVersion::new(1).put::<Pallet<T>>();
+
+		T::DbWeight::get().reads_writes(1, 1)
+	}
+}
+
+pub mod v2 {
+	use crate::pallet::{Config, Pallet};
+	use frame_support::{
+		pallet_prelude::*,
+		storage::migration::{put_storage_value, storage_key_iter},
+		traits::{GetStorageVersion, PalletInfoAccess, StorageVersion},
+		Blake2_128Concat, StorageHasher,
+	};
+	use sp_std::vec::Vec;
+
+	/// `Collection` before tokens could be transferred.
+	#[derive(Decode)]
+	pub struct OldCollection<BlockNumber> {
+		pub total_supply: u32,
+		pub created_at: BlockNumber,
+		pub metadata: Vec<u8>,
+	}
+
+	/// `Collection` at storage version 2.
+	#[derive(Encode, Decode)]
+	pub struct Collection<BlockNumber> {
+		pub total_supply: u32,
+		pub created_at: BlockNumber,
+		pub metadata: Vec<u8>,
+		pub transferable: bool,
+	}
+
+	/// Mark every existing collection soulbound, as all of them were until now.
+	pub fn migrate<T: Config>() -> Weight {
+		let on_chain = Pallet::<T>::on_chain_storage_version();
+		if on_chain != 1 {
+			return T::DbWeight::get().reads(1);
+		}
+
+		let pallet = Pallet::<T>::name();
+		let old: Vec<(u32, OldCollection<T::BlockNumber>)> =
+			storage_key_iter::<u32, OldCollection<T::BlockNumber>, Blake2_128Concat>(pallet.as_bytes(), b"Collections").collect();
+		let translated = old.len() as u64;
+		for (uid, old) in old {
+			let collection = Collection {
+				total_supply: old.total_supply,
+				created_at: old.created_at,
+				metadata: old.metadata,
+				transferable: false,
+			};
+			put_storage_value(pallet.as_bytes(), b"Collections", &uid.using_encoded(Blake2_128Concat::hash), collection);
+		}
+
+		StorageVersion::new(2).put::<Pallet<T>>();
+
+		T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+	}
+}
+
+pub mod v3 {
+	use crate::pallet::{BalanceOf, Config, Pallet};
+	use frame_support::{
+		pallet_prelude::*,
+		storage::migration::{put_storage_value, storage_key_iter},
+		traits::{GetStorageVersion, PalletInfoAccess, StorageVersion},
+		Blake2_128Concat, StorageHasher,
+	};
+	use sp_runtime::traits::Zero;
+	use sp_std::vec::Vec;
+
+	/// `Collection` at storage version 3.
+	#[derive(Encode, Decode)]
+	pub struct Collection<AccountId, BlockNumber, Balance> {
+		pub total_supply: u32,
+		pub created_at: BlockNumber,
+		pub metadata: Vec<u8>,
+		pub transferable: bool,
+		pub owner: Option<AccountId>,
+		pub issuer: Option<AccountId>,
+		pub deposit: Balance,
+	}
+
+	/// Record that existing collections were created by root: they have no owner, issuer or
+	/// deposit.
+	pub fn migrate<T: Config>() -> Weight {
+		let on_chain = Pallet::<T>::on_chain_storage_version();
+		if on_chain != 2 {
+			return T::DbWeight::get().reads(1);
+		}
+
+		let pallet = Pallet::<T>::name();
+		let old: Vec<(u32, super::v2::Collection<T::BlockNumber>)> =
+			storage_key_iter::<u32, super::v2::Collection<T::BlockNumber>, Blake2_128Concat>(pallet.as_bytes(), b"Collections").collect();
+		let translated = old.len() as u64;
+		for (uid, old) in old {
+			let collection = Collection::<T::AccountId, T::BlockNumber, BalanceOf<T>> {
+				total_supply: old.total_supply,
+				created_at: old.created_at,
+				metadata: old.metadata,
+				transferable: old.transferable,
+				owner: None,
+				issuer: None,
+				deposit: Zero::zero(),
+			};
+			put_storage_value(pallet.as_bytes(), b"Collections", &uid.using_encoded(Blake2_128Concat::hash), collection);
+		}
+
+		StorageVersion::new(3).put::<Pallet<T>>();
+
+		T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+	}
+}
+
+pub mod v4 {
+	use crate::pallet::{BalanceOf, Collection, Collections, Config, OwnedTokens, Pallet, Token, Tokens};
+	use frame_support::{
+		pallet_prelude::*,
+		storage::migration::{remove_storage_prefix, storage_key_iter},
+		traits::{GetStorageVersion, PalletInfoAccess, StorageVersion},
+		Blake2_128Concat,
+	};
+	use sp_std::vec::Vec;
+
+	/// Key tokens by collection and token id instead of by owner and collection, and index them
+	/// by owner. Existing collections keep one token per account.
+	pub fn migrate<T: Config>() -> Weight {
+		let on_chain = Pallet::<T>::on_chain_storage_version();
+		if on_chain != 3 {
+			return T::DbWeight::get().reads(1);
+		}
+
+		let mut collections: u64 = 0;
+		Collections::<T>::translate::<super::v3::Collection<T::AccountId, T::BlockNumber, BalanceOf<T>>, _>(|_, old| {
+			collections += 1;
+			Some(Collection::<T> {
+				total_supply: old.total_supply,
+				created_at: old.created_at,
+				metadata: old.metadata,
+				transferable: old.transferable,
+				allow_multiple_per_account: false,
+				owner: old.owner,
+				issuer: old.issuer,
+				deposit: old.deposit,
+			})
+		});
+
+		let pallet = Pallet::<T>::name();
+		let tokens: Vec<((T::AccountId, u32), Token<T>)> =
+			storage_key_iter::<(T::AccountId, u32), Token<T>, Blake2_128Concat>(pallet.as_bytes(), b"Tokens").collect();
+		let moved = tokens.len() as u64;
+		remove_storage_prefix(pallet.as_bytes(), b"Tokens", &[]);
+		for ((owner, collection_id), token) in tokens {
+			OwnedTokens::<T>::insert((owner, collection_id, token.id), ());
+			Tokens::<T>::insert(collection_id, token.id, token);
+		}
+
+		StorageVersion::new(4).put::<Pallet<T>>();
+
+		T::DbWeight::get().reads_writes(collections + moved + 1, collections + 2 * moved + 2)
+	}
+}
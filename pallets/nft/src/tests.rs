@@ -1,12 +1,18 @@
-use crate::{mock::*, Error};
-use frame_support::{assert_noop, assert_ok};
+use crate::{migrations::Migration, mock::*, Error};
+use codec::Encode;
+use frame_support::{
+	assert_noop, assert_ok,
+	storage::migration::put_storage_value,
+	traits::{Currency, GetStorageVersion, OnRuntimeUpgrade, ReservableCurrency, StorageVersion},
+	Blake2_128Concat, StorageHasher,
+};
 
 #[test]
 fn it_creates_a_collection_should_work() {
 	new_test_ext().execute_with(|| {
         assert_eq!(Nft::get_total_collections(), 0);
 		//Dispatch a signed extrinsic.
-		assert_ok!(Nft::create_collection(Origin::root(),1,200,b"Qualifier".to_vec()));
+		assert_ok!(Nft::force_create_collection(Origin::root(),1,None,200,b"Qualifier".to_vec(),false,false));
 		// Read pallet storage and assert an expected result.
 		assert_eq!(Nft::get_total_collections(), 1);
 	});
@@ -17,7 +23,7 @@ fn it_mints_a_token_should_work() {
 	new_test_ext().execute_with(|| {
         assert_eq!(Nft::get_total_collections(), 0);
 		//Dispatch a signed extrinsic.
-		assert_ok!(Nft::create_collection(Origin::root(),1,200,b"Qualifier".to_vec()));
+		assert_ok!(Nft::force_create_collection(Origin::root(),1,None,200,b"Qualifier".to_vec(),false,false));
         //Check # of tokens for collection 1
         assert_eq!(Nft::get_active_tokens(1u32),0u32);
 		//Mint a token
@@ -33,7 +39,7 @@ fn it_mints_a_token_should_fail_1() {
 	new_test_ext().execute_with(|| {
         assert_eq!(Nft::get_total_collections(), 0);
 		//Dispatch a signed extrinsic.
-		assert_ok!(Nft::create_collection(Origin::root(),1,200,b"Qualifier".to_vec()));
+		assert_ok!(Nft::force_create_collection(Origin::root(),1,None,200,b"Qualifier".to_vec(),false,false));
         //Check # of tokens for collection 1
         assert_eq!(Nft::get_active_tokens(1u32),0u32);
 		//Mint a token
@@ -51,7 +57,7 @@ fn it_mints_a_token_should_fail_2() {
 	new_test_ext().execute_with(|| {
         assert_eq!(Nft::get_total_collections(), 0);
 		//Dispatch a signed extrinsic.
-		assert_ok!(Nft::create_collection(Origin::root(),1,1,b"Qualifier".to_vec()));
+		assert_ok!(Nft::force_create_collection(Origin::root(),1,None,1,b"Qualifier".to_vec(),false,false));
         //Check # of tokens for collection 1
         assert_eq!(Nft::get_active_tokens(1u32),0u32);
 		//Mint a token
@@ -69,7 +75,7 @@ fn it_burns_a_token_should_work() {
 	new_test_ext().execute_with(|| {
         assert_eq!(Nft::get_total_collections(), 0);
 		//Dispatch a signed extrinsic.
-		assert_ok!(Nft::create_collection(Origin::root(),1,200,b"Qualifier".to_vec()));
+		assert_ok!(Nft::force_create_collection(Origin::root(),1,None,200,b"Qualifier".to_vec(),false,false));
         
         assert_eq!(Nft::get_active_tokens(1u32),0u32);
 		//Mint a token
@@ -77,7 +83,7 @@ fn it_burns_a_token_should_work() {
         // Verify that that coount increases by 1
         assert_eq!(Nft::get_active_tokens(1u32),1u32);
 		// Token holder burns the token
-		assert_ok!(Nft::burn(Origin::signed(1),1));
+		assert_ok!(Nft::burn(Origin::signed(1),1,1));
 		// Verify that that coount decreases by 1
         assert_eq!(Nft::get_active_tokens(1u32),0u32);
 	});
@@ -88,7 +94,7 @@ fn it_burns_a_token_should_fail() {
 	new_test_ext().execute_with(|| {
         assert_eq!(Nft::get_total_collections(), 0);
 		//Dispatch a signed extrinsic.
-		assert_ok!(Nft::create_collection(Origin::root(),1,200,b"Qualifier".to_vec()));
+		assert_ok!(Nft::force_create_collection(Origin::root(),1,None,200,b"Qualifier".to_vec(),false,false));
         
         assert_eq!(Nft::get_active_tokens(1u32),0u32);
 		//Mint a token
@@ -96,7 +102,348 @@ fn it_burns_a_token_should_fail() {
         // Verify that that coount increases by 1
         assert_eq!(Nft::get_active_tokens(1u32),1u32);
 		// Token holder burns the token
-		assert_noop!(Nft::burn(Origin::signed(1),2),Error::<Test>::TokenDoesNotExist);
+		assert_noop!(Nft::burn(Origin::signed(1),1,2),Error::<Test>::TokenDoesNotExist);
 		
 	});
-}
\ No newline at end of file
+}
+
+#[test]
+fn it_migrates_collections_should_work() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(0).put::<Nft>();
+		let old_collection = (200u32,0u64,b"Qualifier".to_vec());
+		put_storage_value(b"Nft",b"Collections",&1u32.using_encoded(Blake2_128Concat::hash),old_collection);
+
+		Migration::<Test>::on_runtime_upgrade();
+
+		let collection = Nft::get_collection(1).unwrap();
+		assert_eq!(collection.total_supply,200);
+		assert_eq!(collection.metadata,b"Qualifier".to_vec());
+		assert!(!collection.transferable);
+		assert_eq!(collection.owner,None);
+		assert!(!collection.allow_multiple_per_account);
+		assert_eq!(Nft::on_chain_storage_version(),4);
+	});
+}
+
+#[test]
+fn it_transfers_token_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Nft::force_create_collection(Origin::root(),1,None,200,b"Relic".to_vec(),true,false));
+		assert_ok!(Nft::mint(Origin::root(),1,1));
+		assert_ok!(Nft::transfer(Origin::signed(1),1,1,2));
+
+		assert_eq!(Nft::token_of(1,&1),None);
+		let token = Nft::get_token(1,1).unwrap();
+		assert_eq!((token.id,token.owner),(1,2));
+		assert_eq!(Nft::token_of(1,&2),Some(1));
+		assert_eq!(Nft::get_active_tokens(1),1);
+	});
+}
+
+#[test]
+fn it_transfers_token_should_fail() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Nft::force_create_collection(Origin::root(),1,None,200,b"Qualifier".to_vec(),false,false));
+		assert_ok!(Nft::force_create_collection(Origin::root(),2,None,200,b"Relic".to_vec(),true,false));
+		assert_ok!(Nft::mint(Origin::root(),1,1));
+		assert_ok!(Nft::mint(Origin::root(),2,1));
+		assert_ok!(Nft::mint(Origin::root(),2,2));
+
+		assert_noop!(Nft::transfer(Origin::signed(1),1,1,3), Error::<Test>::Soulbound);
+		assert_noop!(Nft::transfer(Origin::signed(1),2,1,2), Error::<Test>::OneAccountOneToken);
+		assert_noop!(Nft::transfer(Origin::signed(3),2,1,4), Error::<Test>::NotTheOwner);
+		assert_noop!(Nft::transfer(Origin::signed(1),2,3,4), Error::<Test>::TokenDoesNotExist);
+		assert_noop!(Nft::transfer(Origin::signed(1),3,1,4), Error::<Test>::CollectionDoesNotExist);
+	});
+}
+
+#[test]
+fn it_checks_active_tokens_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Nft::force_create_collection(Origin::root(),1,None,200,b"Qualifier".to_vec(),false,false));
+		assert_ok!(Nft::mint(Origin::root(),1,1));
+		assert_ok!(Nft::mint(Origin::root(),1,2));
+		assert_ok!(Nft::burn(Origin::signed(1),1,1));
+		assert_ok!(Nft::do_try_state());
+	});
+}
+
+#[test]
+fn it_sets_total_supply_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Nft::force_create_collection(Origin::root(),1,None,2,b"Qualifier".to_vec(),false,false));
+		assert_ok!(Nft::mint(Origin::root(),1,1));
+		assert_ok!(Nft::mint(Origin::root(),1,2));
+		assert_noop!(Nft::mint(Origin::root(),1,3), Error::<Test>::TokenMaxSupplyReached);
+
+		assert_ok!(Nft::set_total_supply(Origin::root(),1,3));
+		assert_ok!(Nft::mint(Origin::root(),1,3));
+		assert_noop!(Nft::set_total_supply(Origin::root(),1,2), Error::<Test>::SupplyBelowActiveTokens);
+	});
+}
+
+#[test]
+fn it_creates_owned_collection_should_work() {
+	new_test_ext().execute_with(|| {
+		Balances::make_free_balance_be(&1,1_000);
+		assert_ok!(Nft::create_collection(Origin::signed(1),10,b"Relic".to_vec(),true,false));
+
+		let collection = Nft::get_collection(5).unwrap();
+		assert_eq!((collection.owner,collection.issuer,collection.deposit),(Some(1),Some(1),100));
+		assert_eq!(Balances::reserved_balance(&1),100);
+
+		// Signed creators are given ids above the reserved ones, skipping any root has taken
+		assert_ok!(Nft::force_create_collection(Origin::root(),6,None,10,b"Relic".to_vec(),true,false));
+		assert_ok!(Nft::create_collection(Origin::signed(1),10,b"Relic".to_vec(),true,false));
+		assert_eq!(Nft::get_collection(7).unwrap().owner,Some(1));
+		assert_eq!(Nft::get_next_collection_id(),8);
+
+		// The issuer mints in its own collection, and can hand issuance over
+		assert_ok!(Nft::mint(Origin::signed(1),5,2));
+		assert_ok!(Nft::set_issuer(Origin::signed(1),5,Some(3)));
+		assert_noop!(Nft::mint(Origin::signed(1),5,4), Error::<Test>::NotTheIssuer);
+		assert_ok!(Nft::mint(Origin::signed(3),5,4));
+		assert_eq!(Nft::get_active_tokens(5),2);
+	});
+}
+
+#[test]
+fn it_creates_owned_collection_should_fail() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(Nft::create_collection(Origin::signed(1),10,b"Relic".to_vec(),true,false), pallet_balances::Error::<Test>::InsufficientBalance);
+		assert_noop!(Nft::force_create_collection(Origin::signed(1),5,None,10,b"Relic".to_vec(),true,false), sp_runtime::DispatchError::BadOrigin);
+
+		Balances::make_free_balance_be(&1,1_000);
+		assert_ok!(Nft::force_create_collection(Origin::root(),1,None,200,b"Qualifier".to_vec(),false,false));
+		assert_noop!(Nft::force_create_collection(Origin::root(),1,None,200,b"Qualifier".to_vec(),false,false), Error::<Test>::CollectionExists);
+		assert_noop!(Nft::create_collection(Origin::signed(1),10,[0u8; 33].to_vec(),true,false), Error::<Test>::MetadataTooLong);
+		assert_noop!(Nft::mint(Origin::signed(1),1,1), Error::<Test>::NotTheIssuer);
+		assert_noop!(Nft::set_issuer(Origin::signed(1),1,Some(1)), Error::<Test>::NotTheCollectionOwner);
+		assert_eq!(Balances::reserved_balance(&1),0);
+	});
+}
+
+#[test]
+fn it_sets_metadata_and_attributes_should_work() {
+	new_test_ext().execute_with(|| {
+		Balances::make_free_balance_be(&1,1_000);
+		assert_ok!(Nft::create_collection(Origin::signed(1),10,b"Relic".to_vec(),true,false));
+		assert_ok!(Nft::mint(Origin::signed(1),5,2));
+
+		assert_ok!(Nft::set_token_metadata(Origin::signed(1),5,1,b"ipfs://relic".to_vec()));
+		assert_eq!(Nft::get_token_metadata(5,1),Some(b"ipfs://relic".to_vec()));
+		assert_ok!(Nft::clear_token_metadata(Origin::signed(1),5,1));
+		assert_eq!(Nft::get_token_metadata(5,1),None);
+
+		assert_ok!(Nft::set_attribute(Origin::signed(1),5,None,b"era".to_vec(),b"1960s".to_vec(),false));
+		assert_ok!(Nft::set_attribute(Origin::signed(1),5,Some(1),b"document".to_vec(),b"42".to_vec(),false));
+		assert_eq!(Nft::get_attribute((5u32,Some(1u32),b"document".to_vec())),Some((b"42".to_vec(),false)));
+		assert_ok!(Nft::clear_attribute(Origin::signed(1),5,None,b"era".to_vec()));
+		assert_eq!(Nft::get_attribute((5u32,None::<u32>,b"era".to_vec())),None);
+
+		// Burning a token removes its metadata and attributes
+		assert_ok!(Nft::set_token_metadata(Origin::signed(1),5,1,b"ipfs://relic".to_vec()));
+		assert_ok!(Nft::burn(Origin::signed(2),5,1));
+		assert_eq!(Nft::get_token_metadata(5,1),None);
+		assert_eq!(Nft::get_attribute((5u32,Some(1u32),b"document".to_vec())),None);
+		assert_eq!(Nft::get_attribute_count(5u32,Some(1u32)),0);
+		assert_ok!(Nft::do_try_state());
+
+		// Root sets attributes on collections it created
+		assert_ok!(Nft::force_create_collection(Origin::root(),1,None,200,b"Qualifier".to_vec(),false,false));
+		assert_ok!(Nft::mint(Origin::root(),1,2));
+		assert_ok!(Nft::set_attribute(Origin::root(),1,Some(1),b"joined".to_vec(),b"12".to_vec(),true));
+	});
+}
+
+#[test]
+fn it_sets_metadata_and_attributes_should_fail() {
+	new_test_ext().execute_with(|| {
+		Balances::make_free_balance_be(&1,1_000);
+		assert_ok!(Nft::create_collection(Origin::signed(1),10,b"Relic".to_vec(),true,false));
+		assert_ok!(Nft::mint(Origin::signed(1),5,2));
+
+		assert_noop!(Nft::set_token_metadata(Origin::signed(2),5,1,b"ipfs://relic".to_vec()), Error::<Test>::NotTheCollectionOwner);
+		assert_noop!(Nft::set_token_metadata(Origin::signed(1),5,2,b"ipfs://relic".to_vec()), Error::<Test>::TokenDoesNotExist);
+		assert_noop!(Nft::set_token_metadata(Origin::signed(1),5,1,vec![0u8;33]), Error::<Test>::MetadataTooLong);
+		assert_noop!(Nft::set_attribute(Origin::signed(1),5,None,vec![0u8;9],b"1960s".to_vec(),false), Error::<Test>::KeyTooLong);
+		assert_noop!(Nft::set_attribute(Origin::signed(1),5,None,b"era".to_vec(),vec![0u8;17],false), Error::<Test>::ValueTooLong);
+
+		// A locked attribute cannot change
+		assert_ok!(Nft::set_attribute(Origin::signed(1),5,Some(1),b"document".to_vec(),b"42".to_vec(),true));
+		assert_noop!(Nft::set_attribute(Origin::signed(1),5,Some(1),b"document".to_vec(),b"43".to_vec(),false), Error::<Test>::AttributeLocked);
+		assert_noop!(Nft::clear_attribute(Origin::signed(1),5,Some(1),b"document".to_vec()), Error::<Test>::AttributeLocked);
+
+		// A token holds up to three attributes; changing one does not count again
+		assert_ok!(Nft::set_attribute(Origin::signed(1),5,Some(1),b"era".to_vec(),b"1960s".to_vec(),false));
+		assert_ok!(Nft::set_attribute(Origin::signed(1),5,Some(1),b"place".to_vec(),b"Harlem".to_vec(),false));
+		assert_ok!(Nft::set_attribute(Origin::signed(1),5,Some(1),b"era".to_vec(),b"1970s".to_vec(),false));
+		assert_noop!(Nft::set_attribute(Origin::signed(1),5,Some(1),b"author".to_vec(),b"Ann".to_vec(),false), Error::<Test>::TooManyAttributes);
+		assert_ok!(Nft::clear_attribute(Origin::signed(1),5,Some(1),b"era".to_vec()));
+		assert_ok!(Nft::set_attribute(Origin::signed(1),5,Some(1),b"author".to_vec(),b"Ann".to_vec(),false));
+		assert_eq!(Nft::get_attribute_count(5u32,Some(1u32)),3);
+
+		// The collection has its own allowance
+		assert_ok!(Nft::set_attribute(Origin::signed(1),5,None,b"era".to_vec(),b"1960s".to_vec(),false));
+		assert_eq!(Nft::get_attribute_count(5u32,None::<u32>),1);
+		assert_ok!(Nft::do_try_state());
+	});
+}
+
+#[test]
+fn it_force_burns_token_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Nft::force_create_collection(Origin::root(),1,None,200,b"Qualifier".to_vec(),false,false));
+		assert_ok!(Nft::mint(Origin::root(),1,1));
+		assert_noop!(Nft::force_burn(Origin::signed(1),1,1), Error::<Test>::NotTheCollectionOwner);
+
+		assert_ok!(Nft::force_burn(Origin::root(),1,1));
+		assert_eq!(Nft::get_token(1,1),None);
+		assert_eq!(Nft::token_of(1,&1),None);
+		assert_eq!(Nft::get_active_tokens(1),0);
+		assert_noop!(Nft::force_burn(Origin::root(),1,1), Error::<Test>::TokenDoesNotExist);
+		assert_noop!(Nft::freeze(Origin::root(),1,1), Error::<Test>::TokenDoesNotExist);
+		assert_noop!(Nft::set_token_metadata(Origin::root(),1,1,b"ipfs://relic".to_vec()), Error::<Test>::TokenDoesNotExist);
+	});
+}
+
+#[test]
+fn it_freezes_tokens_should_work() {
+	new_test_ext().execute_with(|| {
+		Balances::make_free_balance_be(&1,1_000);
+		assert_ok!(Nft::create_collection(Origin::signed(1),10,b"Relic".to_vec(),true,false));
+		assert_ok!(Nft::mint(Origin::signed(1),5,2));
+		assert_ok!(Nft::mint(Origin::signed(1),5,3));
+
+		assert_ok!(Nft::freeze(Origin::signed(1),5,1));
+		assert!(Nft::is_frozen(5,&2));
+		assert!(!Nft::is_frozen(5,&3));
+		assert_noop!(Nft::transfer(Origin::signed(2),5,1,4), Error::<Test>::Frozen);
+		assert_noop!(Nft::burn(Origin::signed(2),5,1), Error::<Test>::Frozen);
+		assert_ok!(Nft::thaw(Origin::signed(1),5,1));
+		assert_ok!(Nft::transfer(Origin::signed(2),5,1,4));
+
+		assert_ok!(Nft::freeze_collection(Origin::signed(1),5));
+		assert!(Nft::is_frozen(5,&3) && Nft::is_frozen(5,&4));
+		assert_noop!(Nft::burn(Origin::signed(3),5,2), Error::<Test>::Frozen);
+		assert_ok!(Nft::thaw_collection(Origin::signed(1),5));
+		assert!(!Nft::is_frozen(5,&3));
+
+		assert_noop!(Nft::freeze(Origin::signed(2),5,1), Error::<Test>::NotTheCollectionOwner);
+		assert_noop!(Nft::freeze(Origin::signed(1),5,3), Error::<Test>::TokenDoesNotExist);
+	});
+}
+
+#[test]
+fn it_migrates_tokens_should_work() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(3).put::<Nft>();
+		let old_collection = (200u32,0u64,b"Relic".to_vec(),true,Some(1u64),Some(1u64),100u128);
+		put_storage_value(b"Nft",b"Collections",&5u32.using_encoded(Blake2_128Concat::hash),old_collection);
+		// Tokens used to be keyed by owner and collection
+		put_storage_value(b"Nft",b"Tokens",&(2u64,5u32).using_encoded(Blake2_128Concat::hash),(1u32,2u64));
+		put_storage_value(b"Nft",b"Tokens",&(3u64,5u32).using_encoded(Blake2_128Concat::hash),(2u32,3u64));
+
+		Migration::<Test>::on_runtime_upgrade();
+
+		assert_eq!(Nft::get_collection(5).unwrap().owner,Some(1));
+		assert_eq!(Nft::get_token(5,2).unwrap().owner,3);
+		assert_eq!(Nft::token_of(5,&2),Some(1));
+		let mut holders = Nft::holders(5);
+		holders.sort();
+		assert_eq!(holders,vec![(1,2),(2,3)]);
+		assert_eq!(Nft::on_chain_storage_version(),4);
+	});
+}
+
+#[test]
+fn it_holds_multiple_tokens_should_work() {
+	new_test_ext().execute_with(|| {
+		Balances::make_free_balance_be(&1,1_000);
+		assert_ok!(Nft::create_collection(Origin::signed(1),10,b"Pass".to_vec(),true,true));
+		assert_ok!(Nft::mint(Origin::signed(1),5,2));
+		assert_ok!(Nft::mint(Origin::signed(1),5,2));
+		assert_ok!(Nft::mint(Origin::signed(1),5,3));
+		assert_eq!(Nft::tokens_of(5,&2).len(),2);
+
+		assert_ok!(Nft::transfer(Origin::signed(3),5,3,2));
+		assert_eq!(Nft::tokens_of(5,&2).len(),3);
+		assert_ok!(Nft::burn(Origin::signed(2),5,1));
+		assert_noop!(Nft::burn(Origin::signed(3),5,2), Error::<Test>::NotTheOwner);
+		assert_eq!(Nft::get_active_tokens(5),2);
+		assert_ok!(Nft::do_try_state());
+	});
+}
+
+#[test]
+fn it_uses_nonfungibles_traits_should_work() {
+	use frame_support::traits::tokens::nonfungibles::{Create, Inspect, InspectEnumerable, Mutate, Transfer};
+
+	new_test_ext().execute_with(|| {
+		assert_ok!(<Nft as Create<u64>>::create_class(&7,&1,&2));
+		assert_eq!(<Nft as Inspect<u64>>::class_owner(&7),Some(1));
+		assert_noop!(<Nft as Mutate<u64>>::mint_into(&7,&2,&3), Error::<Test>::InvalidTokenId);
+		assert_ok!(<Nft as Mutate<u64>>::mint_into(&7,&1,&3));
+		assert_ok!(<Nft as Mutate<u64>>::mint_into(&7,&2,&3));
+		assert_eq!(<Nft as Inspect<u64>>::owner(&7,&1),Some(3));
+
+		assert_ok!(<Nft as Mutate<u64>>::set_attribute(&7,&1,b"era",b"1960s"));
+		assert_eq!(<Nft as Inspect<u64>>::attribute(&7,&1,b"era"),Some(b"1960s".to_vec()));
+
+		assert_ok!(<Nft as Transfer<u64>>::transfer(&7,&1,&4));
+		assert_eq!(<Nft as InspectEnumerable<u64>>::owned(&4).collect::<Vec<_>>(),vec![(7,1)]);
+		assert_eq!(<Nft as InspectEnumerable<u64>>::owned_in_class(&7,&3).collect::<Vec<_>>(),vec![2]);
+
+		assert_ok!(Nft::freeze(Origin::signed(1),7,2));
+		assert!(!<Nft as Inspect<u64>>::can_transfer(&7,&2));
+		assert_noop!(<Nft as Transfer<u64>>::transfer(&7,&2,&4), Error::<Test>::Frozen);
+
+		assert_ok!(<Nft as Mutate<u64>>::burn_from(&7,&1));
+		assert_eq!(<Nft as Inspect<u64>>::owner(&7,&1),None);
+		assert_ok!(Nft::do_try_state());
+	});
+}
+
+#[test]
+fn it_updates_collection_should_work() {
+	new_test_ext().execute_with(|| {
+		Balances::make_free_balance_be(&1,1_000);
+		assert_ok!(Nft::create_collection(Origin::signed(1),10,b"Relic".to_vec(),true,false));
+		assert_ok!(Nft::mint(Origin::signed(1),5,2));
+
+		assert_ok!(Nft::set_collection_metadata(Origin::signed(1),5,b"Relics".to_vec()));
+		assert_eq!(Nft::get_collection(5).unwrap().metadata,b"Relics".to_vec());
+		assert_ok!(Nft::set_total_supply(Origin::signed(1),5,1));
+		assert_eq!(Nft::get_collection(5).unwrap().total_supply,1);
+
+		assert_noop!(Nft::set_collection_metadata(Origin::signed(2),5,b"Mine".to_vec()), Error::<Test>::NotTheCollectionOwner);
+		assert_noop!(Nft::set_collection_metadata(Origin::signed(1),5,vec![0u8;33]), Error::<Test>::MetadataTooLong);
+		assert_noop!(Nft::set_total_supply(Origin::signed(2),5,20), Error::<Test>::NotTheCollectionOwner);
+		assert_noop!(Nft::set_total_supply(Origin::signed(1),5,0), Error::<Test>::SupplyBelowActiveTokens);
+	});
+}
+
+#[test]
+fn it_destroys_collection_should_work() {
+	new_test_ext().execute_with(|| {
+		Balances::make_free_balance_be(&1,1_000);
+		assert_ok!(Nft::create_collection(Origin::signed(1),10,b"Relic".to_vec(),true,false));
+		assert_ok!(Nft::mint(Origin::signed(1),5,2));
+		assert_ok!(Nft::set_attribute(Origin::signed(1),5,None,b"era".to_vec(),b"1960s".to_vec(),true));
+		assert_noop!(Nft::destroy_collection(Origin::signed(1),5,1), Error::<Test>::CollectionNotEmpty);
+
+		assert_ok!(Nft::burn(Origin::signed(2),5,1));
+		assert_noop!(Nft::destroy_collection(Origin::signed(2),5,1), Error::<Test>::NotTheCollectionOwner);
+		// The witness must cover the collection's attributes
+		assert_noop!(Nft::destroy_collection(Origin::signed(1),5,0), Error::<Test>::BadWitness);
+		assert_ok!(Nft::destroy_collection(Origin::signed(1),5,1));
+
+		assert_eq!(Nft::get_collection(5),None);
+		assert_eq!(Nft::get_total_collections(),0);
+		assert_eq!(Nft::get_attribute((5u32,None::<u32>,b"era".to_vec())),None);
+		assert_eq!(Nft::get_attribute_count(5u32,None::<u32>),0);
+		assert_eq!(Balances::reserved_balance(&1),0);
+		assert_noop!(Nft::destroy_collection(Origin::signed(1),5,0), Error::<Test>::CollectionDoesNotExist);
+	});
+}
@@ -45,7 +45,7 @@ use frame_system::{
 	limits::{BlockLength, BlockWeights},
 	EnsureRoot,
 };
-pub use frame_support::traits::EqualPrivilegeOnly;
+pub use frame_support::traits::{EnsureOneOf, EqualPrivilegeOnly};
 pub use pallet_balances::Call as BalancesCall;
 use pallet_im_online::sr25519::AuthorityId as ImOnlineId;
 use pallet_mmr_primitives as mmr;
@@ -121,10 +121,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	//   `spec_version`, and `authoring_version` are the same between Wasm and native.
 	// This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
 	//   the compatible custom types.
-	spec_version: 100,
+	spec_version: 101,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 2,
 	state_version: 1,
 };
 
@@ -452,7 +452,9 @@ where
 			frame_system::CheckEra::<Runtime>::from(era),
 			frame_system::CheckNonce::<Runtime>::from(nonce),
 			frame_system::CheckWeight::<Runtime>::new(),
-			pallet_transaction_payment::ChargeTransactionPayment::<Runtime>::from(tip),
+			pallet_bhdao::fees::WaiveVotingFees::<Runtime, _>::from(
+				pallet_transaction_payment::ChargeTransactionPayment::<Runtime>::from(tip),
+			),
 		);
 		let raw_payload = SignedPayload::new(call, extra)
 			.map_err(|e| {
@@ -684,15 +686,86 @@ impl pallet_sudo::Config for Runtime {
 	type Call = Call;
 }
 
+parameter_types! {
+	pub const MaxRationaleNoteLength: u32 = 128;
+	pub const MaxScore: u8 = 10;
+	pub const AppealDeposit: Balance = 10 * DOLLARS;
+	pub const MaxAppeals: u32 = 2;
+	pub const AppealQuorumIncrease: u32 = 2;
+	pub const FlagDeposit: Balance = 5 * DOLLARS;
+	pub const FlagThreshold: u32 = 3;
+	pub const MaxQualifiers: u32 = 200;
+	pub const MaxCollectors: u32 = 100;
+	pub const MaxContributors: u32 = 1000;
+	pub const MaxMembers: u32 = 2_000;
+	pub const MaxVotesPerRound: u32 = 2_000;
+	pub const MaxBatchSize: u32 = 100;
+	pub const MaxExhibits: u32 = 100;
+	pub const BallotRetentionPeriod: BlockNumber = 30 * DAYS;
+	pub const ExpiryGracePeriod: BlockNumber = 7 * DAYS;
+	pub const MaxRoundsPerBlock: u32 = 20;
+	pub const MaxPanelAssignments: u32 = 5;
+	// One epoch, so the panel is drawn from randomness of an epoch that began after the round opened
+	pub const PanelDrawDelay: BlockNumber = EPOCH_DURATION_IN_BLOCKS;
+	pub const FreeCallsPerEra: u32 = 50;
+	pub const FreeCallEraLength: BlockNumber = 1 * DAYS;
+}
+
 /// Configure the pallet-template in pallets/template.
 impl pallet_bhdao::Config for Runtime {
 	type Event = Event;
 	type Currency = Balances;
+	type MaxRationaleNoteLength = MaxRationaleNoteLength;
+	type MaxScore = MaxScore;
+	type Origin = Origin;
+	type Proposal = Call;
+	type PalletsOrigin = OriginCaller;
+	type Scheduler = Scheduler;
+	type GovernanceOrigin = EnsureOneOf<EnsureRoot<AccountId>, pallet_bhdao::EnsureDao>;
+	type AdminOrigin = pallet_bhdao::EnsureKey<Runtime>;
+	type AppealDeposit = AppealDeposit;
+	type MaxAppeals = MaxAppeals;
+	type AppealQuorumIncrease = AppealQuorumIncrease;
+	type FlagDeposit = FlagDeposit;
+	type FlagThreshold = FlagThreshold;
+	type MaxQualifiers = MaxQualifiers;
+	type MaxCollectors = MaxCollectors;
+	type MaxContributors = MaxContributors;
+	type MaxMembers = MaxMembers;
+	type MaxVotesPerRound = MaxVotesPerRound;
+	type MaxBatchSize = MaxBatchSize;
+	type MaxExhibits = MaxExhibits;
+	type BallotRetentionPeriod = BallotRetentionPeriod;
+	type ExpiryGracePeriod = ExpiryGracePeriod;
+	type MaxRoundsPerBlock = MaxRoundsPerBlock;
+	type Randomness = pallet_babe::RandomnessFromOneEpochAgo<Runtime>;
+	type PanelDrawDelay = PanelDrawDelay;
+	type MaxPanelAssignments = MaxPanelAssignments;
+	type FreeCallsPerEra = FreeCallsPerEra;
+	type FreeCallEraLength = FreeCallEraLength;
+	// No proxy or multisig pallet is configured, so no accounts are related.
+	type Relations = ();
 }
 
 /// Configure the pallet-nft.
+parameter_types! {
+	pub const CollectionDeposit: Balance = 10 * DOLLARS;
+	pub const NftMetadataLimit: u32 = 256;
+	pub const NftKeyLimit: u32 = 32;
+	pub const NftValueLimit: u32 = 256;
+	pub const NftMaxAttributes: u32 = 32;
+	pub const NftReservedCollections: u32 = 1_000;
+}
+
 impl pallet_nft::Config for Runtime {
 	type Event = Event;
+	type Currency = Balances;
+	type CollectionDeposit = CollectionDeposit;
+	type MetadataLimit = NftMetadataLimit;
+	type KeyLimit = NftKeyLimit;
+	type ValueLimit = NftValueLimit;
+	type MaxAttributes = NftMaxAttributes;
+	type ReservedCollections = NftReservedCollections;
 }
 
 // Create the runtime by composing the FRAME pallets that were previously configured.
@@ -747,12 +820,15 @@ pub type SignedExtra = (
 	frame_system::CheckEra<Runtime>,
 	frame_system::CheckNonce<Runtime>,
 	frame_system::CheckWeight<Runtime>,
-	pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
+	pallet_bhdao::fees::WaiveVotingFees<Runtime, pallet_transaction_payment::ChargeTransactionPayment<Runtime>>,
 );
 /// Unchecked extrinsic type as expected by this runtime.
 pub type UncheckedExtrinsic = generic::UncheckedExtrinsic<Address, Call, Signature, SignedExtra>;
 /// The payload being signed in transactions.
 pub type SignedPayload = generic::SignedPayload<Call, SignedExtra>;
+/// Storage migrations run on runtime upgrade, before every pallet's `on_runtime_upgrade`.
+pub type Migrations =
+	(pallet_bhdao::migrations::Migration<Runtime>, pallet_nft::migrations::Migration<Runtime>);
 /// Executive: handles dispatch to the various modules.
 pub type Executive = frame_executive::Executive<
 	Runtime,
@@ -760,6 +836,7 @@ pub type Executive = frame_executive::Executive<
 	frame_system::ChainContext<Runtime>,
 	Runtime,
 	AllPalletsWithSystem,
+	Migrations,
 >;
 
 #[cfg(feature = "runtime-benchmarks")]
@@ -958,6 +1035,12 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_bhdao_runtime_api::BhdaoApi<Block, AccountId> for Runtime {
+		fn member_profile(who: AccountId) -> Option<pallet_bhdao::MemberProfile> {
+			Bhdao::get_member_profile(who)
+		}
+	}
+
 	impl beefy_primitives::BeefyApi<Block> for Runtime {
 		fn validator_set() -> Option<beefy_primitives::ValidatorSet<BeefyId>> {
 			Beefy::validator_set()